@@ -1,182 +1,140 @@
 #![allow(non_snake_case)]
 
-use ggez::{graphics, Context, GameResult};
-#[derive(Clone)]
-pub struct Assets {
-    pub aceHeart: graphics::Image,
-    pub twoHeart: graphics::Image,
-    pub threeHeart: graphics::Image,
-    pub fourHeart: graphics::Image,
-    pub fiveHeart: graphics::Image,
-    pub sixHeart: graphics::Image,
-    pub sevenHeart: graphics::Image,
-    pub eightHeart: graphics::Image,
-    pub nineHeart: graphics::Image,
-    pub tenHeart: graphics::Image,
-    pub jackHeart: graphics::Image,
-    pub queenHeart: graphics::Image,
-    pub kingHeart: graphics::Image,
+use crate::assets::embedded;
+use crate::assets::procedural::Procedural;
+use crate::assets::theme::{Renderer, Theme};
+use crate::card::card::Card;
+use crate::card::rank::RANKS;
+use crate::card::suit::SUITS;
+use ggez::graphics::{Canvas, Color, Image};
+use ggez::{graphics, Context, GameError, GameResult};
 
-    pub aceDiamond: graphics::Image,
-    pub twoDiamond: graphics::Image,
-    pub threeDiamond: graphics::Image,
-    pub fourDiamond: graphics::Image,
-    pub fiveDiamond: graphics::Image,
-    pub sixDiamond: graphics::Image,
-    pub sevenDiamond: graphics::Image,
-    pub eightDiamond: graphics::Image,
-    pub nineDiamond: graphics::Image,
-    pub tenDiamond: graphics::Image,
-    pub jackDiamond: graphics::Image,
-    pub queenDiamond: graphics::Image,
-    pub kingDiamond: graphics::Image,
+/// Loads an image from the resources, falling back to the one compiled into the game
+fn loadImage(ctx: &Context, path: &str) -> GameResult<Image> {
+    let bytes = embedded::read(ctx, path)?;
+    Image::from_bytes(ctx, &bytes).map_err(|error| GameError::ResourceLoadError(format!("{}: {}", path, error)))
+}
 
-    pub aceClub: graphics::Image,
-    pub twoClub: graphics::Image,
-    pub threeClub: graphics::Image,
-    pub fourClub: graphics::Image,
-    pub fiveClub: graphics::Image,
-    pub sixClub: graphics::Image,
-    pub sevenClub: graphics::Image,
-    pub eightClub: graphics::Image,
-    pub nineClub: graphics::Image,
-    pub tenClub: graphics::Image,
-    pub jackClub: graphics::Image,
-    pub queenClub: graphics::Image,
-    pub kingClub: graphics::Image,
+#[derive(Clone)]
+/// The images the cards of a theme are drawn with
+struct Images {
+    /// The faces of the 52 cards, indexed by `Card::key`
+    faces: Vec<Image>,
+    /// The card back
+    back: Image,
+    /// The outline of an empty final pile
+    frame: Image,
+}
 
-    pub aceSpade: graphics::Image,
-    pub twoSpade: graphics::Image,
-    pub threeSpade: graphics::Image,
-    pub fourSpade: graphics::Image,
-    pub fiveSpade: graphics::Image,
-    pub sixSpade: graphics::Image,
-    pub sevenSpade: graphics::Image,
-    pub eightSpade: graphics::Image,
-    pub nineSpade: graphics::Image,
-    pub tenSpade: graphics::Image,
-    pub jackSpade: graphics::Image,
-    pub queenSpade: graphics::Image,
-    pub kingSpade: graphics::Image,
-    pub back: graphics::Image,
+impl Images {
+    /// Returns the image a card shows: its face or the back
+    fn of(&self, card: &Card) -> &Image {
+        if card.isFaceUp {
+            &self.faces[card.key()]
+        } else {
+            &self.back
+        }
+    }
+}
 
-    pub frame: graphics::Image,
+#[derive(Clone)]
+/// How the cards of a theme are drawn
+enum Look {
+    Images(Images),
+    Procedural(Procedural),
+}
+
+#[derive(Clone)]
+/// Draws the cards of a theme
+pub struct Assets {
+    /// The images or the shapes of the cards
+    look: Look,
+    /// The theme the cards were loaded from
+    pub theme: Theme,
 }
 
 impl Assets {
-    pub fn new(ctx: &mut Context) -> GameResult<Assets> {
-        let aceHeart = graphics::Image::from_path(ctx, "/ace_of_hearts.png")?;
-        let twoHeart = graphics::Image::from_path(ctx, "/2_of_hearts.png")?;
-        let threeHeart = graphics::Image::from_path(ctx, "/3_of_hearts.png")?;
-        let fourHeart = graphics::Image::from_path(ctx, "/4_of_hearts.png")?;
-        let fiveHeart = graphics::Image::from_path(ctx, "/5_of_hearts.png")?;
-        let sixHeart = graphics::Image::from_path(ctx, "/6_of_hearts.png")?;
-        let sevenHeart = graphics::Image::from_path(ctx, "/7_of_hearts.png")?;
-        let eightHeart = graphics::Image::from_path(ctx, "/8_of_hearts.png")?;
-        let nineHeart = graphics::Image::from_path(ctx, "/9_of_hearts.png")?;
-        let tenHeart = graphics::Image::from_path(ctx, "/10_of_hearts.png")?;
-        let jackHeart = graphics::Image::from_path(ctx, "/jack_of_hearts.png")?;
-        let queenHeart = graphics::Image::from_path(ctx, "/queen_of_hearts.png")?;
-        let kingHeart = graphics::Image::from_path(ctx, "/king_of_hearts.png")?;
+    /// Loads the images of a theme, or builds its shapes if it is drawn procedurally
+    pub fn new(ctx: &mut Context, theme: Theme) -> GameResult<Assets> {
+        let look = match theme.renderer {
+            Renderer::Images => {
+                let mut faces = Vec::with_capacity(SUITS.len() * RANKS.len());
+                for suit in SUITS {
+                    for rank in RANKS {
+                        faces.push(loadImage(ctx, &theme.face(suit, rank))?);
+                    }
+                }
+                Look::Images(Images {
+                    faces,
+                    back: loadImage(ctx, &theme.back())?,
+                    frame: loadImage(ctx, &theme.frame())?,
+                })
+            }
+            Renderer::Procedural => Look::Procedural(Procedural::new(ctx, &theme)?),
+        };
+        Ok(Assets { look, theme })
+    }
 
-        let aceDiamond = graphics::Image::from_path(ctx, "/ace_of_diamonds.png")?;
-        let twoDiamond = graphics::Image::from_path(ctx, "/2_of_diamonds.png")?;
-        let threeDiamond = graphics::Image::from_path(ctx, "/3_of_diamonds.png")?;
-        let fourDiamond = graphics::Image::from_path(ctx, "/4_of_diamonds.png")?;
-        let fiveDiamond = graphics::Image::from_path(ctx, "/5_of_diamonds.png")?;
-        let sixDiamond = graphics::Image::from_path(ctx, "/6_of_diamonds.png")?;
-        let sevenDiamond = graphics::Image::from_path(ctx, "/7_of_diamonds.png")?;
-        let eightDiamond = graphics::Image::from_path(ctx, "/8_of_diamonds.png")?;
-        let nineDiamond = graphics::Image::from_path(ctx, "/9_of_diamonds.png")?;
-        let tenDiamond = graphics::Image::from_path(ctx, "/10_of_diamonds.png")?;
-        let jackDiamond = graphics::Image::from_path(ctx, "/jack_of_diamonds.png")?;
-        let queenDiamond = graphics::Image::from_path(ctx, "/queen_of_diamonds.png")?;
-        let kingDiamond = graphics::Image::from_path(ctx, "/king_of_diamonds.png")?;
+    /// Sets the number of window pixels per table unit, which the procedural cards are rendered at
+    pub fn setScale(&mut self, scale: f32) {
+        if let Look::Procedural(procedural) = &mut self.look {
+            procedural.setScale(scale);
+        }
+    }
 
-        let aceClub = graphics::Image::from_path(ctx, "/ace_of_clubs.png")?;
-        let twoClub = graphics::Image::from_path(ctx, "/2_of_clubs.png")?;
-        let threeClub = graphics::Image::from_path(ctx, "/3_of_clubs.png")?;
-        let fourClub = graphics::Image::from_path(ctx, "/4_of_clubs.png")?;
-        let fiveClub = graphics::Image::from_path(ctx, "/5_of_clubs.png")?;
-        let sixClub = graphics::Image::from_path(ctx, "/6_of_clubs.png")?;
-        let sevenClub = graphics::Image::from_path(ctx, "/7_of_clubs.png")?;
-        let eightClub = graphics::Image::from_path(ctx, "/8_of_clubs.png")?;
-        let nineClub = graphics::Image::from_path(ctx, "/9_of_clubs.png")?;
-        let tenClub = graphics::Image::from_path(ctx, "/10_of_clubs.png")?;
-        let jackClub = graphics::Image::from_path(ctx, "/jack_of_clubs.png")?;
-        let queenClub = graphics::Image::from_path(ctx, "/queen_of_clubs.png")?;
-        let kingClub = graphics::Image::from_path(ctx, "/king_of_clubs.png")?;
+    /// Returns the scale that draws an image at the card size of the theme
+    fn cardScale(&self, image: &Image) -> [f32; 2] {
+        [
+            self.theme.cardWidth / image.width() as f32,
+            self.theme.cardHeight / image.height() as f32,
+        ]
+    }
+
+    /// Draws a card on a given canvas, multiplying its colors by the tint (WHITE keeps them unchanged)
+    pub fn drawCard(&self, canvas: &mut Canvas, card: &Card, tint: Color) {
+        match &self.look {
+            Look::Images(images) => {
+                let image = images.of(card);
+                canvas.draw(
+                    image,
+                    graphics::DrawParam::default()
+                        .dest([card.x, card.y])
+                        .scale(self.cardScale(image))
+                        .color(tint),
+                );
+            }
+            Look::Procedural(procedural) => procedural.drawCard(canvas, card, 1.0, tint),
+        }
+    }
+
+    /// Draws a card squeezed horizontally around its center (`scale` 1 is the full width),
+    /// used while the card turns over
+    pub fn drawCardScaled(&self, canvas: &mut Canvas, card: &Card, scale: f32) {
+        match &self.look {
+            Look::Images(images) => {
+                let image = images.of(card);
+                let [scaleX, scaleY] = self.cardScale(image);
+                let x = card.x + self.theme.cardWidth * (1.0 - scale) / 2.0;
+                canvas.draw(
+                    image,
+                    graphics::DrawParam::default().dest([x, card.y]).scale([scaleX * scale, scaleY]),
+                );
+            }
+            Look::Procedural(procedural) => procedural.drawCard(canvas, card, scale, Color::WHITE),
+        }
+    }
 
-        let aceSpade = graphics::Image::from_path(ctx, "/ace_of_spades.png")?;
-        let twoSpade = graphics::Image::from_path(ctx, "/2_of_spades.png")?;
-        let threeSpade = graphics::Image::from_path(ctx, "/3_of_spades.png")?;
-        let fourSpade = graphics::Image::from_path(ctx, "/4_of_spades.png")?;
-        let fiveSpade = graphics::Image::from_path(ctx, "/5_of_spades.png")?;
-        let sixSpade = graphics::Image::from_path(ctx, "/6_of_spades.png")?;
-        let sevenSpade = graphics::Image::from_path(ctx, "/7_of_spades.png")?;
-        let eightSpade = graphics::Image::from_path(ctx, "/8_of_spades.png")?;
-        let nineSpade = graphics::Image::from_path(ctx, "/9_of_spades.png")?;
-        let tenSpade = graphics::Image::from_path(ctx, "/10_of_spades.png")?;
-        let jackSpade = graphics::Image::from_path(ctx, "/jack_of_spades.png")?;
-        let queenSpade = graphics::Image::from_path(ctx, "/queen_of_spades.png")?;
-        let kingSpade = graphics::Image::from_path(ctx, "/king_of_spades.png")?;
-        let back = graphics::Image::from_path(ctx, "/back.png")?;
-        let frame = graphics::Image::from_path(ctx, "/frame.png")?;
-        Ok(Assets {
-            aceHeart,
-            twoHeart,
-            threeHeart,
-            fourHeart,
-            fiveHeart,
-            sixHeart,
-            sevenHeart,
-            eightHeart,
-            nineHeart,
-            tenHeart,
-            jackHeart,
-            queenHeart,
-            kingHeart,
-            aceDiamond,
-            twoDiamond,
-            threeDiamond,
-            fourDiamond,
-            fiveDiamond,
-            sixDiamond,
-            sevenDiamond,
-            eightDiamond,
-            nineDiamond,
-            tenDiamond,
-            jackDiamond,
-            queenDiamond,
-            kingDiamond,
-            aceClub,
-            twoClub,
-            threeClub,
-            fourClub,
-            fiveClub,
-            sixClub,
-            sevenClub,
-            eightClub,
-            nineClub,
-            tenClub,
-            jackClub,
-            queenClub,
-            kingClub,
-            aceSpade,
-            twoSpade,
-            threeSpade,
-            fourSpade,
-            fiveSpade,
-            sixSpade,
-            sevenSpade,
-            eightSpade,
-            nineSpade,
-            tenSpade,
-            jackSpade,
-            queenSpade,
-            kingSpade,
-            back,
-            frame,
-        })
+    /// Draws the outline of an empty pile at (x, y)
+    pub fn drawFrame(&self, canvas: &mut Canvas, x: f32, y: f32, tint: Color) {
+        match &self.look {
+            Look::Images(images) => canvas.draw(
+                &images.frame,
+                graphics::DrawParam::default()
+                    .dest([x, y])
+                    .scale(self.cardScale(&images.frame))
+                    .color(tint),
+            ),
+            Look::Procedural(procedural) => procedural.drawFrame(canvas, x, y, tint),
+        }
     }
 }
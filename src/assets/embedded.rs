@@ -0,0 +1,95 @@
+#![allow(non_snake_case)]
+
+use ggez::{Context, GameError, GameResult};
+use std::io::Read;
+
+/// Pairs the path of a resource with its contents, read from `resources` at compile time
+macro_rules! embed {
+    ($path:literal) => {
+        (
+            concat!("/", $path),
+            include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/resources/", $path)) as &[u8],
+        )
+    };
+}
+
+/// The resources compiled into the game, so that it runs without the `resources` directory
+const FILES: &[(&str, &[u8])] = &[
+    embed!("themes/classic/theme.toml"),
+    embed!("themes/classic/10_of_clubs.png"),
+    embed!("themes/classic/10_of_diamonds.png"),
+    embed!("themes/classic/10_of_hearts.png"),
+    embed!("themes/classic/10_of_spades.png"),
+    embed!("themes/classic/2_of_clubs.png"),
+    embed!("themes/classic/2_of_diamonds.png"),
+    embed!("themes/classic/2_of_hearts.png"),
+    embed!("themes/classic/2_of_spades.png"),
+    embed!("themes/classic/3_of_clubs.png"),
+    embed!("themes/classic/3_of_diamonds.png"),
+    embed!("themes/classic/3_of_hearts.png"),
+    embed!("themes/classic/3_of_spades.png"),
+    embed!("themes/classic/4_of_clubs.png"),
+    embed!("themes/classic/4_of_diamonds.png"),
+    embed!("themes/classic/4_of_hearts.png"),
+    embed!("themes/classic/4_of_spades.png"),
+    embed!("themes/classic/5_of_clubs.png"),
+    embed!("themes/classic/5_of_diamonds.png"),
+    embed!("themes/classic/5_of_hearts.png"),
+    embed!("themes/classic/5_of_spades.png"),
+    embed!("themes/classic/6_of_clubs.png"),
+    embed!("themes/classic/6_of_diamonds.png"),
+    embed!("themes/classic/6_of_hearts.png"),
+    embed!("themes/classic/6_of_spades.png"),
+    embed!("themes/classic/7_of_clubs.png"),
+    embed!("themes/classic/7_of_diamonds.png"),
+    embed!("themes/classic/7_of_hearts.png"),
+    embed!("themes/classic/7_of_spades.png"),
+    embed!("themes/classic/8_of_clubs.png"),
+    embed!("themes/classic/8_of_diamonds.png"),
+    embed!("themes/classic/8_of_hearts.png"),
+    embed!("themes/classic/8_of_spades.png"),
+    embed!("themes/classic/9_of_clubs.png"),
+    embed!("themes/classic/9_of_diamonds.png"),
+    embed!("themes/classic/9_of_hearts.png"),
+    embed!("themes/classic/9_of_spades.png"),
+    embed!("themes/classic/ace_of_clubs.png"),
+    embed!("themes/classic/ace_of_diamonds.png"),
+    embed!("themes/classic/ace_of_hearts.png"),
+    embed!("themes/classic/ace_of_spades.png"),
+    embed!("themes/classic/back.png"),
+    embed!("themes/classic/frame.png"),
+    embed!("themes/classic/jack_of_clubs.png"),
+    embed!("themes/classic/jack_of_diamonds.png"),
+    embed!("themes/classic/jack_of_hearts.png"),
+    embed!("themes/classic/jack_of_spades.png"),
+    embed!("themes/classic/king_of_clubs.png"),
+    embed!("themes/classic/king_of_diamonds.png"),
+    embed!("themes/classic/king_of_hearts.png"),
+    embed!("themes/classic/king_of_spades.png"),
+    embed!("themes/classic/queen_of_clubs.png"),
+    embed!("themes/classic/queen_of_diamonds.png"),
+    embed!("themes/classic/queen_of_hearts.png"),
+    embed!("themes/classic/queen_of_spades.png"),
+    embed!("themes/midnight/theme.toml"),
+    embed!("themes/vector/theme.toml"),
+];
+
+/// Returns the paths of the resources compiled into the game
+pub fn paths() -> impl Iterator<Item = &'static str> {
+    FILES.iter().map(|(path, _)| *path)
+}
+
+/// Reads a resource, preferring a file in the resources or the user data directory
+/// (which overrides the one compiled into the game)
+pub fn read(ctx: &Context, path: &str) -> GameResult<Vec<u8>> {
+    if ctx.fs.is_file(path) {
+        let mut bytes = Vec::new();
+        ctx.fs.open(path)?.read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+    FILES
+        .iter()
+        .find(|(candidate, _)| *candidate == path)
+        .map(|(_, bytes)| bytes.to_vec())
+        .ok_or_else(|| GameError::ResourceLoadError(format!("{} is missing", path)))
+}
@@ -1 +1,4 @@
-pub mod assets;
\ No newline at end of file
+pub mod assets;
+pub mod embedded;
+pub mod procedural;
+pub mod theme;
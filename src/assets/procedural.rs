@@ -0,0 +1,299 @@
+#![allow(non_snake_case)]
+
+use crate::assets::theme::Theme;
+use crate::card::card::Card;
+use crate::card::color::Color as CardColor;
+use crate::card::rank::Rank;
+use crate::card::suit::{Suit, SUITS};
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect, Text, TextLayout};
+use ggez::{Context, GameResult};
+use std::f32::consts::PI;
+
+/// The color of hearts and diamonds
+const RED: Color = Color::new(0.8, 0.1, 0.12, 1.0);
+/// The color of clubs and spades
+const BLACK: Color = Color::new(0.1, 0.1, 0.1, 1.0);
+/// The color of the edge of a card
+const EDGE: Color = Color::new(0.55, 0.55, 0.55, 1.0);
+/// The color of the outline of an empty pile
+const FRAME: Color = Color::new(1.0, 1.0, 1.0, 0.6);
+/// The radius of the corners, as a share of the card width
+const CORNER: f32 = 0.08;
+/// The x coordinate of the center of the corner index, as a share of the card width
+const INDEX_X: f32 = 0.12;
+/// The y coordinates of the centers of the rank and of the pip of the corner index, as shares of the card height
+const INDEX_RANK_Y: f32 = 0.1;
+const INDEX_PIP_Y: f32 = 0.23;
+/// The height of the rank of the corner index, as a share of the card height
+const INDEX_SIZE: f32 = 0.16;
+/// The size of the pip of the corner index, as a share of the card width
+const INDEX_PIP_SIZE: f32 = 0.13;
+/// The size of a pip of a number card, as a share of the card width
+const PIP_SIZE: f32 = 0.18;
+/// The size of the pip of an ace and of the letter of a face card, as a share of the card width
+const LARGE_SIZE: f32 = 0.5;
+/// The accuracy of the curves of the suits, in pip sizes
+const TOLERANCE: f32 = 0.005;
+
+/// Draws the cards from shapes and text instead of images, so that they are sharp at any size
+#[derive(Clone)]
+pub struct Procedural {
+    /// The size of a card in table units
+    width: f32,
+    height: f32,
+    /// The white front of a card
+    face: Mesh,
+    /// The back of a card
+    back: Mesh,
+    /// The outline of an empty pile
+    frame: Mesh,
+    /// The shape of every suit in the order of SUITS, 1 wide and high and centered on the origin
+    suits: Vec<Mesh>,
+    /// The number of window pixels per table unit, at which the text is rendered
+    pixelScale: f32,
+}
+
+impl Procedural {
+    /// Builds the shapes of the cards of a theme
+    pub fn new(ctx: &Context, theme: &Theme) -> GameResult<Procedural> {
+        let (width, height) = (theme.cardWidth, theme.cardHeight);
+        let bounds = Rect::new(0.0, 0.0, width, height);
+        let radius = width * CORNER;
+        let [red, green, blue] = theme.backColor;
+
+        let face = Mesh::from_data(
+            ctx,
+            MeshBuilder::new()
+                .rounded_rectangle(DrawMode::fill(), bounds, radius, Color::WHITE)?
+                .rounded_rectangle(DrawMode::stroke(1.0), bounds, radius, EDGE)?
+                .build(),
+        );
+        let inset = width * 0.1;
+        let inner = Rect::new(inset, inset, width - 2.0 * inset, height - 2.0 * inset);
+        let back = Mesh::from_data(
+            ctx,
+            MeshBuilder::new()
+                .rounded_rectangle(DrawMode::fill(), bounds, radius, Color::WHITE)?
+                .rounded_rectangle(DrawMode::fill(), inner, radius / 2.0, Color::from_rgb(red, green, blue))?
+                .rounded_rectangle(DrawMode::stroke(1.0), bounds, radius, EDGE)?
+                .build(),
+        );
+        let frame = Mesh::from_data(
+            ctx,
+            MeshBuilder::new().rounded_rectangle(DrawMode::stroke(2.0), bounds, radius, FRAME)?.build(),
+        );
+        let suits = SUITS
+            .into_iter()
+            .map(|suit| Ok(Mesh::from_data(ctx, suitShape(suit)?.build())))
+            .collect::<GameResult<Vec<Mesh>>>()?;
+
+        Ok(Procedural {
+            width,
+            height,
+            face,
+            back,
+            frame,
+            suits,
+            pixelScale: 1.0,
+        })
+    }
+
+    /// Sets the number of window pixels per table unit, so that the text stays sharp
+    pub fn setScale(&mut self, scale: f32) {
+        self.pixelScale = scale.max(0.1);
+    }
+
+    /// Draws a card squeezed horizontally around its center (`squeeze` 1 is the full width),
+    /// multiplying its colors by the tint (WHITE keeps them unchanged)
+    pub fn drawCard(&self, canvas: &mut Canvas, card: &Card, squeeze: f32, tint: Color) {
+        let (width, height) = (self.width, self.height);
+        let place = Placement {
+            left: card.x + width * (1.0 - squeeze) / 2.0,
+            top: card.y,
+            squeeze,
+            tint,
+            textColor: multiply(suitColor(card.suit), tint),
+        };
+        let whole = DrawParam::default().dest(place.at([0.0, 0.0])).scale([squeeze, 1.0]).color(tint);
+        if !card.isFaceUp {
+            canvas.draw(&self.back, whole);
+            return;
+        }
+        canvas.draw(&self.face, whole);
+
+        let suit = &self.suits[card.suit as usize];
+        let rank = card.rank.label();
+        // The index is upright in the top left corner and upside down in the bottom right one
+        for (x, y, sign, rotation) in [(width * INDEX_X, 0.0, 1.0, 0.0), (width * (1.0 - INDEX_X), height, -1.0, PI)] {
+            let rankCenter = [x, y + sign * height * INDEX_RANK_Y];
+            self.drawText(canvas, &place, rank, rankCenter, height * INDEX_SIZE, rotation);
+            let pipCenter = [x, y + sign * height * INDEX_PIP_Y];
+            self.drawPip(canvas, &place, suit, pipCenter, width * INDEX_PIP_SIZE, rotation);
+        }
+
+        let center = [width / 2.0, height / 2.0];
+        match card.rank {
+            Rank::Ace => self.drawPip(canvas, &place, suit, center, width * LARGE_SIZE, 0.0),
+            Rank::Jack | Rank::Queen | Rank::King => {
+                self.drawText(canvas, &place, rank, center, width * LARGE_SIZE * 1.2, 0.0)
+            }
+            _ => {
+                for &(column, row) in pips(card.rank) {
+                    // The pips of the lower half point down, like on printed cards
+                    let rotation = if row > 0.5 { PI } else { 0.0 };
+                    self.drawPip(canvas, &place, suit, [width * column, height * row], width * PIP_SIZE, rotation);
+                }
+            }
+        }
+    }
+
+    /// Draws the outline of an empty pile at (x, y)
+    pub fn drawFrame(&self, canvas: &mut Canvas, x: f32, y: f32, tint: Color) {
+        canvas.draw(&self.frame, DrawParam::default().dest([x, y]).color(tint));
+    }
+
+    /// Draws the shape of a suit `size` wide around a point of a card
+    fn drawPip(&self, canvas: &mut Canvas, place: &Placement, suit: &Mesh, center: [f32; 2], size: f32, rotation: f32) {
+        let param = DrawParam::default()
+            .dest(place.at(center))
+            .rotation(rotation)
+            .scale([size * place.squeeze, size])
+            .color(place.tint);
+        canvas.draw(suit, param);
+    }
+
+    /// Draws a text `size` high around a point of a card, rendered at the window resolution
+    fn drawText(&self, canvas: &mut Canvas, place: &Placement, label: &str, center: [f32; 2], size: f32, rotation: f32) {
+        let mut text = Text::new(label);
+        text.set_scale(size * self.pixelScale).set_layout(TextLayout::center());
+        let param = DrawParam::default()
+            .dest(place.at(center))
+            .rotation(rotation)
+            .scale([place.squeeze / self.pixelScale, 1.0 / self.pixelScale])
+            .color(place.textColor);
+        canvas.draw(&text, param);
+    }
+}
+
+/// Where and how a card is drawn
+struct Placement {
+    /// The left side of the squeezed card
+    left: f32,
+    /// The top of the card
+    top: f32,
+    /// The share of its width the card is drawn with
+    squeeze: f32,
+    /// The color the card is multiplied by
+    tint: Color,
+    /// The color of the ranks, already tinted
+    textColor: Color,
+}
+
+impl Placement {
+    /// Turns a point of the card into a point of the table
+    fn at(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        [self.left + x * self.squeeze, self.top + y]
+    }
+}
+
+/// Returns the shape of a suit, 1 wide and high and centered on the origin
+fn suitShape(suit: Suit) -> GameResult<MeshBuilder> {
+    let color = suitColor(suit);
+    let fill = DrawMode::fill();
+    let mut shape = MeshBuilder::new();
+    match suit {
+        Suit::Heart => {
+            shape
+                .circle(fill, [-0.22, -0.17], 0.25, TOLERANCE, color)?
+                .circle(fill, [0.22, -0.17], 0.25, TOLERANCE, color)?
+                .triangles(&[[-0.45, -0.08], [0.45, -0.08], [0.0, 0.5]], color)?;
+        }
+        Suit::Diamond => {
+            shape.polygon(fill, &[[0.0, -0.5], [0.38, 0.0], [0.0, 0.5], [-0.38, 0.0]], color)?;
+        }
+        Suit::Club => {
+            shape
+                .circle(fill, [0.0, -0.24], 0.22, TOLERANCE, color)?
+                .circle(fill, [-0.24, 0.08], 0.22, TOLERANCE, color)?
+                .circle(fill, [0.24, 0.08], 0.22, TOLERANCE, color)?
+                .triangles(&[[0.0, 0.0], [0.16, 0.5], [-0.16, 0.5]], color)?;
+        }
+        Suit::Spade => {
+            shape
+                .circle(fill, [-0.22, 0.12], 0.24, TOLERANCE, color)?
+                .circle(fill, [0.22, 0.12], 0.24, TOLERANCE, color)?
+                .triangles(&[[-0.45, 0.05], [0.45, 0.05], [0.0, -0.5], [0.0, 0.1], [0.16, 0.5], [-0.16, 0.5]], color)?;
+        }
+    }
+    Ok(shape)
+}
+
+/// Returns the color a suit is drawn in
+fn suitColor(suit: Suit) -> Color {
+    match suit.getColor() {
+        CardColor::Red => RED,
+        CardColor::Black => BLACK,
+    }
+}
+
+/// Multiplies two colors channel by channel, like a tint does
+fn multiply(color: Color, tint: Color) -> Color {
+    Color::new(color.r * tint.r, color.g * tint.g, color.b * tint.b, color.a * tint.a)
+}
+
+/// Returns the centers of the pips of a number card, as shares of the card width and height
+fn pips(rank: Rank) -> &'static [(f32, f32)] {
+    const LEFT: f32 = 0.28;
+    const MIDDLE: f32 = 0.5;
+    const RIGHT: f32 = 0.72;
+    match rank {
+        Rank::Two => &[(MIDDLE, 0.2), (MIDDLE, 0.8)],
+        Rank::Three => &[(MIDDLE, 0.2), (MIDDLE, 0.5), (MIDDLE, 0.8)],
+        Rank::Four => &[(LEFT, 0.2), (RIGHT, 0.2), (LEFT, 0.8), (RIGHT, 0.8)],
+        Rank::Five => &[(LEFT, 0.2), (RIGHT, 0.2), (MIDDLE, 0.5), (LEFT, 0.8), (RIGHT, 0.8)],
+        Rank::Six => &[(LEFT, 0.2), (RIGHT, 0.2), (LEFT, 0.5), (RIGHT, 0.5), (LEFT, 0.8), (RIGHT, 0.8)],
+        Rank::Seven => &[
+            (LEFT, 0.2),
+            (RIGHT, 0.2),
+            (MIDDLE, 0.35),
+            (LEFT, 0.5),
+            (RIGHT, 0.5),
+            (LEFT, 0.8),
+            (RIGHT, 0.8),
+        ],
+        Rank::Eight => &[
+            (LEFT, 0.2),
+            (RIGHT, 0.2),
+            (MIDDLE, 0.35),
+            (LEFT, 0.5),
+            (RIGHT, 0.5),
+            (MIDDLE, 0.65),
+            (LEFT, 0.8),
+            (RIGHT, 0.8),
+        ],
+        Rank::Nine => &[
+            (LEFT, 0.2),
+            (RIGHT, 0.2),
+            (LEFT, 0.4),
+            (RIGHT, 0.4),
+            (MIDDLE, 0.5),
+            (LEFT, 0.6),
+            (RIGHT, 0.6),
+            (LEFT, 0.8),
+            (RIGHT, 0.8),
+        ],
+        Rank::Ten => &[
+            (LEFT, 0.2),
+            (RIGHT, 0.2),
+            (MIDDLE, 0.3),
+            (LEFT, 0.4),
+            (RIGHT, 0.4),
+            (LEFT, 0.6),
+            (RIGHT, 0.6),
+            (MIDDLE, 0.7),
+            (LEFT, 0.8),
+            (RIGHT, 0.8),
+        ],
+        _ => &[],
+    }
+}
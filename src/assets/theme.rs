@@ -0,0 +1,171 @@
+#![allow(non_snake_case)]
+
+use crate::assets::embedded;
+use crate::card::card::{CARD_HEIGHT, CARD_WIDTH};
+use crate::card::rank::Rank;
+use crate::card::suit::Suit;
+use crate::game::preferences::DEFAULT_THEME;
+use ggez::graphics::Color;
+use ggez::{Context, GameError, GameResult};
+use serde::Deserialize;
+
+/// The directory (in the resources or the user data directory) that holds one directory per theme
+const THEMES_DIRECTORY: &str = "/themes";
+/// The directory name of the theme that draws the cards from shapes
+const FALLBACK_THEME: &str = "vector";
+/// The name of the manifest of a theme
+const MANIFEST: &str = "theme.toml";
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+/// How the cards of a theme are drawn
+pub enum Renderer {
+    /// From the images named in the manifest
+    #[default]
+    Images,
+    /// From shapes and text, sharp at any card size and needing no files
+    Procedural,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+/// The look of the cards and the table, read from the `theme.toml` of a theme directory
+pub struct Theme {
+    /// The name of the directory of the theme, which identifies it
+    #[serde(skip)]
+    pub id: String,
+    /// The name shown on the settings screen
+    pub name: String,
+    /// How the cards are drawn (the paths of the images are not used by the procedural renderer)
+    pub renderer: Renderer,
+    /// The path of the card faces, with {rank} and {suit} in it
+    pub faces: String,
+    /// The path of the card back
+    pub back: String,
+    /// The path of the outline of an empty final pile
+    pub frame: String,
+    /// The color of the table, in red, green and blue
+    pub tableColor: [u8; 3],
+    /// The color of the card back of the procedural renderer
+    pub backColor: [u8; 3],
+    /// The size of a card on the table
+    pub cardWidth: f32,
+    pub cardHeight: f32,
+    /// The vertical distance between two face-up cards of a pile, if there is room
+    pub fanOffset: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            id: DEFAULT_THEME.to_string(),
+            name: "Classic".to_string(),
+            renderer: Renderer::Images,
+            faces: "{rank}_of_{suit}.png".to_string(),
+            back: "back.png".to_string(),
+            frame: "frame.png".to_string(),
+            tableColor: [0, 128, 0],
+            backColor: [150, 30, 40],
+            cardWidth: CARD_WIDTH,
+            cardHeight: CARD_HEIGHT,
+            fanOffset: 25.0,
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the theme used when no theme can be loaded, which draws the cards without any file
+    pub fn fallback() -> Theme {
+        Theme {
+            id: FALLBACK_THEME.to_string(),
+            name: "Vector".to_string(),
+            renderer: Renderer::Procedural,
+            ..Theme::default()
+        }
+    }
+
+    /// Reads the manifest of the theme in the directory `id`
+    pub fn load(ctx: &Context, id: &str) -> GameResult<Theme> {
+        let path = format!("{}/{}/{}", THEMES_DIRECTORY, id, MANIFEST);
+        let manifest = String::from_utf8(embedded::read(ctx, &path)?)
+            .map_err(|error| GameError::ResourceLoadError(format!("{}: {}", path, error)))?;
+        let mut theme: Theme = toml::from_str(&manifest)
+            .map_err(|error| GameError::ResourceLoadError(format!("{}: {}", path, error)))?;
+        if theme.cardWidth <= 0.0 || theme.cardHeight <= 0.0 || theme.fanOffset <= 0.0 {
+            return Err(GameError::ResourceLoadError(format!(
+                "{}: the card size and the fan offset must be positive",
+                path
+            )));
+        }
+        theme.id = id.to_string();
+        Ok(theme)
+    }
+
+    /// Returns the themes that can be loaded, sorted by name
+    pub fn available(ctx: &Context) -> Vec<Theme> {
+        let onDisk = ctx.fs.read_dir(THEMES_DIRECTORY).into_iter().flatten();
+        let mut ids: Vec<String> = onDisk
+            .filter_map(|entry| entry.file_name().map(|name| name.to_string_lossy().into_owned()))
+            .collect();
+        let prefix = format!("{}/", THEMES_DIRECTORY);
+        let compiledIn = embedded::paths().filter_map(|path| path.strip_prefix(&prefix)?.split('/').next());
+        ids.extend(compiledIn.map(String::from));
+        // A theme can be in the resources, in the user data directory and compiled into the game
+        ids.sort();
+        ids.dedup();
+        let mut themes: Vec<Theme> = ids.iter().filter_map(|id| Theme::load(ctx, id).ok()).collect();
+        themes.sort_by(|a, b| a.name.cmp(&b.name));
+        themes
+    }
+
+    /// Turns a path of the manifest into a path of the resources
+    fn resolve(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("{}/{}/{}", THEMES_DIRECTORY, self.id, path)
+        }
+    }
+
+    /// Returns the path of the face of a card
+    pub fn face(&self, suit: Suit, rank: Rank) -> String {
+        let rank = match rank {
+            Rank::Ace => "ace",
+            Rank::Two => "2",
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
+            Rank::Six => "6",
+            Rank::Seven => "7",
+            Rank::Eight => "8",
+            Rank::Nine => "9",
+            Rank::Ten => "10",
+            Rank::Jack => "jack",
+            Rank::Queen => "queen",
+            Rank::King => "king",
+        };
+        let suit = match suit {
+            Suit::Heart => "hearts",
+            Suit::Diamond => "diamonds",
+            Suit::Club => "clubs",
+            Suit::Spade => "spades",
+        };
+        self.resolve(&self.faces.replace("{rank}", rank).replace("{suit}", suit))
+    }
+
+    /// Returns the path of the card back
+    pub fn back(&self) -> String {
+        self.resolve(&self.back)
+    }
+
+    /// Returns the path of the outline of an empty final pile
+    pub fn frame(&self) -> String {
+        self.resolve(&self.frame)
+    }
+
+    /// Returns the color of the table
+    pub fn tableColor(&self) -> Color {
+        let [red, green, blue] = self.tableColor;
+        Color::from_rgb(red, green, blue)
+    }
+}
@@ -0,0 +1,75 @@
+#![allow(non_snake_case)]
+
+use solitaire::engine::engine::Engine;
+use solitaire::script::command::Command;
+use solitaire::script::driver::{Driver, Outcome, USAGE_ERROR};
+use solitaire::script::options::{Options, USAGE};
+use solitaire::solver::result::SolverResult;
+use solitaire::solver::solver::Solver;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::{env, process};
+
+/// The exit status of `--solve` when the solver gave up before a verdict
+const SOLVER_GAVE_UP: i32 = 4;
+
+fn main() {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(USAGE_ERROR);
+        }
+    };
+    let engine = match options.position {
+        Some(engine) => engine,
+        None => Engine::withSeed(options.difficulty, options.deal.unwrap_or_else(rand::random)),
+    };
+    if options.solve {
+        process::exit(solve(&engine));
+    }
+
+    let input: Box<dyn BufRead> = match &options.script {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("solitaire-cli: cannot read {}: {}", path.display(), error);
+                process::exit(USAGE_ERROR);
+            }
+        },
+        None => Box::new(io::stdin().lock()),
+    };
+    let mut driver = Driver::new(engine, options.quiet);
+    let outcome = match driver.run(input, &mut io::stdout().lock()) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            eprintln!("solitaire-cli: {}", error);
+            process::exit(USAGE_ERROR);
+        }
+    };
+    if let Outcome::Failed { line, text, error } = &outcome {
+        eprintln!("solitaire-cli: line {}: {}: {}", line, text, error);
+    }
+    process::exit(outcome.exitCode());
+}
+
+/// Prints the script that wins the game and returns the exit status:
+/// 0 if the game can be won, 1 if it cannot and 4 if the solver gave up
+fn solve(engine: &Engine) -> i32 {
+    match Solver::default().solve(engine) {
+        SolverResult::Won(moves) => {
+            for command in moves.iter().filter_map(Command::fromMove) {
+                println!("{}", command);
+            }
+            0
+        }
+        SolverResult::Unwinnable => {
+            eprintln!("solitaire-cli: the game cannot be won");
+            1
+        }
+        SolverResult::Unknown => {
+            eprintln!("solitaire-cli: the solver gave up before finding a win");
+            SOLVER_GAVE_UP
+        }
+    }
+}
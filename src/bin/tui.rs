@@ -0,0 +1,32 @@
+#![allow(non_snake_case)]
+
+use std::process;
+
+#[cfg(unix)]
+fn main() {
+    use solitaire::tui::app::App;
+    use solitaire::tui::options::{Options, USAGE};
+    use solitaire::tui::terminal::Terminal;
+    use std::env;
+
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+    // The terminal is restored before an error is printed
+    let result = Terminal::open().and_then(|mut terminal| App::new(options).run(&mut terminal));
+    if let Err(error) = result {
+        eprintln!("solitaire-tui: {}", error);
+        process::exit(1);
+    }
+}
+
+/// The terminal is driven through termios, which only Unix has
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("solitaire-tui: the terminal front end only runs on Unix");
+    process::exit(1);
+}
@@ -1,34 +0,0 @@
-/// The x coordinate of pile 1
-pub const PILE1_X: f32 = 85.0;
-/// The x coordinate of pile 2
-pub const PILE2_X: f32 = 260.0;
-/// The x coordinate of pile 3
-pub const PILE3_X: f32 = 435.0;
-/// The x coordinate of pile 4
-pub const PILE4_X: f32 = 610.0;
-/// The x coordinate of pile 5
-pub const PILE5_X: f32 = 785.0;
-/// The x coordinate of pile 6
-pub const PILE6_X: f32 = 960.0;
-/// The x coordinate of pile 7
-pub const PILE7_X: f32 = 1135.0;
-/// The y coordinate of the piles from pile1 to pile7
-pub const PILE_Y: f32 = 300.0;
-/// The x coordinate of the deck
-pub const DECK_X: f32 = 85.0;
-/// The y coordinate of the deck
-pub const DECK_Y: f32 = 30.0;
-/// The x coordinate of the discard pile
-pub const DISCARD_X: f32 = 185.0;
-/// The y coordinate of the discard pile
-pub const DISCARD_Y: f32 = 30.0;
-/// The x coordinate of final1
-pub const FINAL1_X: f32 = 435.0;
-/// The x coordinate of final2
-pub const FINAL2_X: f32 = 610.0;
-/// The x coordinate of final3
-pub const FINAL3_X: f32 = 785.0;
-/// The x coordinate of final4
-pub const FINAL4_X: f32 = 960.0;
-/// The y coordinate of the piles from final1 to final4
-pub const FINAL_Y: f32 = 30.0;
@@ -1,4 +1,8 @@
-#[derive(Clone, PartialEq, Debug)]
+#![allow(non_snake_case)]
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 /// Indicates the type of the pile
 pub enum PileIdentifier {
     Deck,
@@ -16,3 +20,30 @@ pub enum PileIdentifier {
     Pile7,
     GrabbedCards,
 }
+
+impl PileIdentifier {
+    /// Checks if the identifier is one of pile1 to pile7
+    pub fn isPile(&self) -> bool {
+        matches!(
+            self,
+            PileIdentifier::Pile1
+                | PileIdentifier::Pile2
+                | PileIdentifier::Pile3
+                | PileIdentifier::Pile4
+                | PileIdentifier::Pile5
+                | PileIdentifier::Pile6
+                | PileIdentifier::Pile7
+        )
+    }
+
+    /// Checks if the identifier is one of final1 to final4
+    pub fn isFinal(&self) -> bool {
+        matches!(
+            self,
+            PileIdentifier::Final1
+                | PileIdentifier::Final2
+                | PileIdentifier::Final3
+                | PileIdentifier::Final4
+        )
+    }
+}
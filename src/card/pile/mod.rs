@@ -1,3 +1,2 @@
 pub mod pile;
 pub mod identifier;
-pub mod coordinates;
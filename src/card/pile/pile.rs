@@ -2,21 +2,25 @@
 
 use crate::card::card::Card;
 use crate::card::pile::identifier::PileIdentifier;
-use ggez::graphics::Canvas;
 use crate::card::pile::identifier::PileIdentifier::{Final1, Final2, Final3, Final4, Pile1, Pile2, Pile3, Pile4, Pile5, Pile6, Pile7};
 
+/// The default vertical distance between two cards of a fanned pile
 pub const CARD_OFFSET: f32 = 12.5;
 /// An array of all pile identifiers
 pub const PILE_IDENTIFIERS: [PileIdentifier; 7] = [Pile1, Pile2, Pile3, Pile4, Pile5, Pile6, Pile7];
 /// An array of all final identifiers
 pub const FINAL_IDENTIFIERS: [PileIdentifier; 4] = [Final1, Final2, Final3, Final4];
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Pile {
     /// The x coordinate of the pile
     pub x: f32,
     /// The y coordinate of the pile
     pub y: f32,
+    /// The vertical distance between a face-up card added WITH offset and the card on it
+    pub offset: f32,
+    /// The vertical distance between a face-down card added WITH offset and the card on it
+    pub faceDownOffset: f32,
     /// The pile identifier
     pub identifier: PileIdentifier,
     /// The cards in the pile
@@ -29,20 +33,31 @@ impl Pile {
         Pile {
             x,
             y,
+            offset: CARD_OFFSET,
+            faceDownOffset: CARD_OFFSET,
             identifier,
             cards: Vec::new(),
         }
     }
 
     /// Adds a card to the pile WITH offset (Changes x, y, initialX, initialY and initialPile of the card)
-    pub fn addCard(&mut self, card: Card) {
+    pub fn addCard(&mut self, mut card: Card) {
+        let y = self.nextY();
+        card.x = self.x;
+        card.y = y;
+        card.initialX = self.x;
+        card.initialY = y;
+        card.initialPile = self.identifier;
         self.cards.push(card);
-        let index = self.cards.len() - 1;
-        self.cards[index].x = self.x;
-        self.cards[index].y = self.y + index as f32 * CARD_OFFSET;
-        self.cards[index].initialX = self.x;
-        self.cards[index].initialY = self.y + index as f32 * CARD_OFFSET;
-        self.cards[index].initialPile = self.identifier.clone();
+    }
+
+    /// Returns the y coordinate of the next card added WITH offset
+    pub fn nextY(&self) -> f32 {
+        match self.getTopCard() {
+            Some(card) if card.isFaceUp => card.y + self.offset,
+            Some(card) => card.y + self.faceDownOffset,
+            None => self.y,
+        }
     }
 
     /// Adds a card to the pile WITHOUT offset (Changes x, y, initialX, initialY and initialPile of the card)
@@ -51,7 +66,7 @@ impl Pile {
         card.y = self.y;
         card.initialX = self.x;
         card.initialY = self.y;
-        card.initialPile = self.identifier.clone();
+        card.initialPile = self.identifier;
         self.cards.push(card);
     }
 
@@ -60,7 +75,26 @@ impl Pile {
         self.cards.push(card);
         let index = self.cards.len() - 1;
         self.cards[index].x = self.x;
-        self.cards[index].y = self.y + (self.cards.len() - 1) as f32 * CARD_OFFSET;
+        self.cards[index].y = self.y + (self.cards.len() - 1) as f32 * self.offset;
+    }
+
+    /// Moves the pile and its cards to (x, y). If `fanned`, each card is put `offset` below
+    /// a face-up card and `faceDownOffset` below a face-down one.
+    pub fn place(&mut self, x: f32, y: f32, faceDownOffset: f32, offset: f32, fanned: bool) {
+        self.x = x;
+        self.y = y;
+        self.faceDownOffset = faceDownOffset;
+        self.offset = offset;
+        let mut cardY = y;
+        for card in self.cards.iter_mut() {
+            card.x = x;
+            card.y = cardY;
+            card.initialX = card.x;
+            card.initialY = card.y;
+            if fanned {
+                cardY += if card.isFaceUp { offset } else { faceDownOffset };
+            }
+        }
     }
 
     /// Removes the top card of the pile adn returns Option<Card>
@@ -95,13 +129,6 @@ impl Pile {
         }
     }
 
-    /// Draws a pile on the given canvas
-    pub fn drawPile(&self, canvas: &mut Canvas) {
-        for card in &self.cards {
-            card.drawCard(canvas);
-        }
-    }
-
     /// Returns the number of cards in the pile
     pub fn size(&self) -> usize {
         self.cards.len()
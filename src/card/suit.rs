@@ -1,8 +1,9 @@
 #![allow(non_snake_case)]
 
 use crate::card::color::Color;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 /// Indicates the suit of the card
 pub enum Suit {
     Heart,
@@ -11,6 +12,9 @@ pub enum Suit {
     Spade,
 }
 
+/// All suits, in the order of the card table of the assets
+pub const SUITS: [Suit; 4] = [Suit::Heart, Suit::Diamond, Suit::Club, Suit::Spade];
+
 impl Suit {
     /// Returns the color of a suit
     pub fn getColor(&self) -> Color {
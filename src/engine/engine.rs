@@ -0,0 +1,941 @@
+#![allow(non_snake_case)]
+
+use crate::card::card::Card;
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::pile::pile::{Pile, FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
+use crate::card::rank::{Rank, RANKS};
+use crate::card::suit::{Suit, SUITS};
+use crate::engine::error::{MoveError, PositionError};
+use crate::engine::history::History;
+use crate::engine::moves::Move;
+use crate::engine::scoring::Scoring;
+use crate::engine::shuffle::shuffle;
+use crate::game::difficulty::Difficulty;
+use crate::game::difficulty::Difficulty::Easy;
+
+use std::mem;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+/// The Klondike rules and the 13 piles they operate on, without any graphics
+pub struct Engine {
+    /// Indicates if the game is over
+    gameOver: bool,
+    /// The first pile (left -> right)
+    pile1: Pile,
+    /// The second pile (left -> right)
+    pile2: Pile,
+    /// The third pile (left -> right)
+    pile3: Pile,
+    /// The fourth pile (left -> right)
+    pile4: Pile,
+    /// The fifth pile (left -> right)
+    pile5: Pile,
+    /// The sixth pile (left -> right)
+    pile6: Pile,
+    /// The seventh pile (left -> right)
+    pile7: Pile,
+    /// The deck
+    deck: Pile,
+    /// The discard pile
+    discard: Pile,
+    /// The first final pile (left -> right)
+    final1: Pile,
+    /// The second final pile (left -> right)
+    final2: Pile,
+    /// The third final pile (left -> right)
+    final3: Pile,
+    /// The fourth final pile (left -> right)
+    final4: Pile,
+    /// Indicates the game difficulty
+    difficulty: Difficulty,
+    /// The seed the deck was shuffled with (the deal number), None for a position that was not dealt
+    seed: Option<u64>,
+    /// The rule set the score is kept with
+    scoring: Scoring,
+    /// The current score
+    score: i32,
+    /// The number of times the discard pile was turned over into the deck
+    recycles: u32,
+    /// The number of moves made so far
+    moves: u32,
+    /// The time spent playing so far
+    elapsed: Duration,
+    /// Indicates if undoing (and redoing) a move counts as a move, rather than taking it back from the count
+    undoCountsAsMove: bool,
+    /// The undo/redo history (snapshots in it always have an empty history of their own)
+    history: History,
+}
+
+impl Engine {
+    /// Creates a new engine with a randomly shuffled and dealt deck
+    pub fn new(difficulty: Difficulty) -> Engine {
+        Self::withSeed(difficulty, rand::random())
+    }
+
+    /// Creates a new engine with the deal derived from the given seed
+    pub fn withSeed(difficulty: Difficulty, seed: u64) -> Engine {
+        Self::withScoring(difficulty, Scoring::default(), seed)
+    }
+
+    /// Creates a new engine that keeps the score with the given rule set
+    pub fn withScoring(difficulty: Difficulty, scoring: Scoring, seed: u64) -> Engine {
+        let mut engine = Engine::empty(difficulty, Some(seed));
+        engine.scoring = scoring;
+        engine.score = scoring.initialScore();
+
+        engine.newDeck(seed);
+        engine.dealCards();
+
+        engine
+    }
+
+    /// Creates an engine from the cards of each pile (bottom card first).
+    /// The coordinates of the cards are recalculated and every pile that is not listed is empty.
+    pub fn fromPiles(
+        difficulty: Difficulty,
+        seed: Option<u64>,
+        piles: Vec<(PileIdentifier, Vec<Card>)>,
+    ) -> Result<Engine, PositionError> {
+        let mut engine = Engine::empty(difficulty, seed);
+        let mut seen: Vec<PileIdentifier> = Vec::new();
+        let mut cards: Vec<(Suit, Rank)> = Vec::new();
+
+        for (identifier, pileCards) in piles {
+            if identifier == GrabbedCards || seen.contains(&identifier) {
+                return Err(PositionError::InvalidPile(identifier));
+            }
+            seen.push(identifier);
+            let pile = engine.identifyPileMut(identifier);
+            for card in pileCards {
+                let key = (card.suit, card.rank);
+                if cards.contains(&key) {
+                    return Err(PositionError::InvalidCards);
+                }
+                cards.push(key);
+                if identifier.isPile() {
+                    pile.addCard(card);
+                } else {
+                    pile.addNoOffset(card);
+                }
+            }
+        }
+        if cards.len() != 52 {
+            return Err(PositionError::InvalidCards);
+        }
+
+        engine.checkForWin();
+        Ok(engine)
+    }
+
+    /// Checks if the cards can be on the pile in a game: the deck is face down, the discard pile face up,
+    /// a final pile builds up by suit from the Ace and a pile has its face-down cards below a run of face-up ones
+    /// that goes down by one rank and alternates in color
+    pub fn isPlayable(identifier: PileIdentifier, cards: &[Card]) -> bool {
+        match identifier {
+            Deck => cards.iter().all(|card| !card.isFaceUp),
+            Discard => cards.iter().all(|card| card.isFaceUp),
+            Final1 | Final2 | Final3 | Final4 => cards
+                .iter()
+                .enumerate()
+                .all(|(index, card)| card.isFaceUp && card.suit == cards[0].suit && card.rank as usize == index),
+            _ => cards.windows(2).all(|pair| match (pair[0].isFaceUp, pair[1].isFaceUp) {
+                (false, _) => true,
+                (true, false) => false,
+                (true, true) => {
+                    pair[0].suit.getColor() != pair[1].suit.getColor()
+                        && pair[0].rank as usize == pair[1].rank as usize + 1
+                }
+            }),
+        }
+    }
+
+    /// Creates an engine without any cards, with every pile at (0, 0) until a front end places it
+    fn empty(difficulty: Difficulty, seed: Option<u64>) -> Engine {
+        Engine {
+            gameOver: false,
+            pile1: Pile::new(0.0, 0.0, Pile1),
+            pile2: Pile::new(0.0, 0.0, Pile2),
+            pile3: Pile::new(0.0, 0.0, Pile3),
+            pile4: Pile::new(0.0, 0.0, Pile4),
+            pile5: Pile::new(0.0, 0.0, Pile5),
+            pile6: Pile::new(0.0, 0.0, Pile6),
+            pile7: Pile::new(0.0, 0.0, Pile7),
+            deck: Pile::new(0.0, 0.0, Deck),
+            discard: Pile::new(0.0, 0.0, Discard),
+            final1: Pile::new(0.0, 0.0, Final1),
+            final2: Pile::new(0.0, 0.0, Final2),
+            final3: Pile::new(0.0, 0.0, Final3),
+            final4: Pile::new(0.0, 0.0, Final4),
+            difficulty,
+            seed,
+            scoring: Scoring::default(),
+            score: 0,
+            recycles: 0,
+            moves: 0,
+            elapsed: Duration::ZERO,
+            undoCountsAsMove: true,
+            history: History::default(),
+        }
+    }
+
+    /// Generates a new deck with 52 cards and then shuffles it with the given seed
+    fn newDeck(&mut self, seed: u64) {
+        let mut deck: Vec<Card> = Vec::new();
+        for suit in SUITS {
+            for rank in RANKS {
+                deck.push(Card::new(suit, rank));
+            }
+        }
+
+        shuffle(&mut deck, seed);
+
+        for card in deck {
+            self.deck.addNoOffset(card);
+        }
+    }
+
+    /// "Deals" the cards from a generated deck to the piles and the rest remain in the deck
+    fn dealCards(&mut self) {
+        for i in 0..7 {
+            for j in 0..i + 1 {
+                let mut card = self.deck.removeCard().unwrap();
+                if j == i {
+                    card.isFaceUp = true;
+                }
+                match i {
+                    0 => self.pile1.addCard(card),
+                    1 => self.pile2.addCard(card),
+                    2 => self.pile3.addCard(card),
+                    3 => self.pile4.addCard(card),
+                    4 => self.pile5.addCard(card),
+                    5 => self.pile6.addCard(card),
+                    6 => self.pile7.addCard(card),
+                    _ => panic!("Invalid pile"),
+                }
+            }
+        }
+    }
+
+    /// Returns the game difficulty
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Returns the seed the deck was shuffled with (the deal number), None for a position that was not dealt
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns the rule set the score is kept with
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Returns the current score
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns the score including the time bonus for a won game
+    pub fn finalScore(&self) -> i32 {
+        if self.gameOver {
+            self.score + self.scoring.timeBonus(self.elapsed)
+        } else {
+            self.score
+        }
+    }
+
+    /// Returns the number of times the discard pile was turned over into the deck
+    pub fn recycles(&self) -> u32 {
+        self.recycles
+    }
+
+    /// Returns how many more times the discard pile may be turned over (None if there is no limit)
+    pub fn recyclesLeft(&self) -> Option<u32> {
+        self.scoring
+            .maxRecycles(self.difficulty)
+            .map(|max| max.saturating_sub(self.recycles))
+    }
+
+    /// Returns the number of moves made so far
+    pub fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// Returns the time spent playing so far
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Adds time spent playing. The clock stops once the game is over.
+    pub fn tick(&mut self, delta: Duration) {
+        if !self.gameOver {
+            self.elapsed += delta;
+        }
+    }
+
+    /// Sets if undoing (and redoing) a move counts as a move, rather than taking it back from the count
+    pub fn setUndoCountsAsMove(&mut self, undoCountsAsMove: bool) {
+        self.undoCountsAsMove = undoCountsAsMove;
+    }
+
+    /// Restores the score, the counters and the clock of a saved game
+    pub(crate) fn restoreProgress(
+        &mut self,
+        scoring: Scoring,
+        score: i32,
+        recycles: u32,
+        moves: u32,
+        elapsed: Duration,
+    ) {
+        self.scoring = scoring;
+        self.score = score;
+        self.recycles = recycles;
+        self.moves = moves;
+        self.elapsed = elapsed;
+    }
+
+    /// Indicates if the game is over
+    pub fn isGameOver(&self) -> bool {
+        self.gameOver
+    }
+
+    /// Checks for win conditions
+    fn checkForWin(&mut self) {
+        self.gameOver = self.final1.size() == 13
+            && self.final2.size() == 13
+            && self.final3.size() == 13
+            && self.final4.size() == 13;
+    }
+
+    /// Identifies a pile by a given PileIdentifier and returns a reference to the pile.
+    /// Panics for GrabbedCards, which is not owned by the engine.
+    pub fn identifyPile(&self, identifier: PileIdentifier) -> &Pile {
+        match identifier {
+            Pile1 => &self.pile1,
+            Pile2 => &self.pile2,
+            Pile3 => &self.pile3,
+            Pile4 => &self.pile4,
+            Pile5 => &self.pile5,
+            Pile6 => &self.pile6,
+            Pile7 => &self.pile7,
+            Final1 => &self.final1,
+            Final2 => &self.final2,
+            Final3 => &self.final3,
+            Final4 => &self.final4,
+            Deck => &self.deck,
+            Discard => &self.discard,
+            GrabbedCards => panic!("GrabbedCards is not owned by the engine"),
+        }
+    }
+
+    /// Moves a pile and its cards to (x, y). The cards of pile1 to pile7 are fanned:
+    /// each card is put `offset` below a face-up card and `faceDownOffset` below a face-down one.
+    pub fn placePile(&mut self, identifier: PileIdentifier, x: f32, y: f32, faceDownOffset: f32, offset: f32) {
+        let fanned = identifier.isPile();
+        self.identifyPileMut(identifier).place(x, y, faceDownOffset, offset, fanned);
+    }
+
+    /// Identifies a pile by a given PileIdentifier and returns a mutable reference to the pile
+    fn identifyPileMut(&mut self, identifier: PileIdentifier) -> &mut Pile {
+        match identifier {
+            Pile1 => &mut self.pile1,
+            Pile2 => &mut self.pile2,
+            Pile3 => &mut self.pile3,
+            Pile4 => &mut self.pile4,
+            Pile5 => &mut self.pile5,
+            Pile6 => &mut self.pile6,
+            Pile7 => &mut self.pile7,
+            Final1 => &mut self.final1,
+            Final2 => &mut self.final2,
+            Final3 => &mut self.final3,
+            Final4 => &mut self.final4,
+            Deck => &mut self.deck,
+            Discard => &mut self.discard,
+            GrabbedCards => panic!("GrabbedCards is not owned by the engine"),
+        }
+    }
+
+    /// A helper function for canBeStacked
+    fn stackableCheckForFinal(&self, card: &Card, pile: &Pile) -> bool {
+        match pile.getTopCard() {
+            None => card.rank == Rank::Ace,
+            Some(topCard) => {
+                card.suit == topCard.suit
+                    && card.rank as i32 == topCard.rank as i32 + 1
+            }
+        }
+    }
+
+    /// A helper function for canBeStacked
+    fn stackableCheckForPile(&self, card: &Card, pile: &Pile) -> bool {
+        match pile.getTopCard() {
+            None => card.rank == Rank::King,
+            Some(topCard) => {
+                card.suit.getColor() != topCard.suit.getColor()
+                    && card.rank as i32 == topCard.rank as i32 - 1
+            }
+        }
+    }
+
+    /// A function that checks if a given card can be placed on a given pile
+    pub fn canBeStacked(&self, card: &Card, receiverIdentifier: PileIdentifier) -> bool {
+        match receiverIdentifier {
+            Final1 | Final2 | Final3 | Final4 => {
+                self.stackableCheckForFinal(card, self.identifyPile(receiverIdentifier))
+            }
+            Pile1 | Pile2 | Pile3 | Pile4 | Pile5 | Pile6 | Pile7 => {
+                self.stackableCheckForPile(card, self.identifyPile(receiverIdentifier))
+            }
+            _ => false,
+        }
+    }
+
+    /// A function that removes all cards from the discard pile, flips them and then returns them in the deck pile
+    fn emptyDiscard(&mut self) {
+        let mut cards = self.discard.removeCards();
+        for card in cards.iter_mut() {
+            card.isFaceUp = false;
+            self.deck.addNoOffset(*card);
+        }
+    }
+
+    /// A function that handles card discarding.
+    fn discardCard(&mut self) {
+        let count = if self.difficulty == Easy { 1 } else { 3 };
+        let mut iterations: usize = 0;
+
+        while iterations < count && !self.deck.empty() {
+            let mut card = self.deck.removeCard().unwrap();
+            card.isFaceUp = true;
+            self.discard.addNoOffset(card);
+            iterations += 1;
+        }
+    }
+
+    /// A helper function for validate that checks moving the top `count` cards of one pile onto another
+    fn validateTransfer(&self, from: &PileIdentifier, to: &PileIdentifier, count: usize) -> Result<(), MoveError> {
+        if from == to {
+            return Err(MoveError::InvalidPile(*to));
+        }
+        let source = self.identifyPile(*from);
+        if count == 0 || count > source.size() {
+            return Err(MoveError::NotEnoughCards(*from));
+        }
+        let moved = &source.cards[source.size() - count..];
+        if moved.iter().any(|card| !card.isFaceUp) {
+            return Err(MoveError::FaceDown(*from));
+        }
+        if !self.canBeStacked(&moved[0], *to) {
+            return Err(MoveError::CannotBeStacked(*to));
+        }
+        Ok(())
+    }
+
+    /// Checks if a move is allowed by the rules without applying it
+    pub fn validate(&self, action: &Move) -> Result<(), MoveError> {
+        if self.gameOver {
+            return Err(MoveError::GameOver);
+        }
+        let checkPile = |identifier: &PileIdentifier| {
+            if identifier.isPile() {
+                Ok(())
+            } else {
+                Err(MoveError::InvalidPile(*identifier))
+            }
+        };
+        let checkFinal = |identifier: &PileIdentifier| {
+            if identifier.isFinal() {
+                Ok(())
+            } else {
+                Err(MoveError::InvalidPile(*identifier))
+            }
+        };
+        match action {
+            Move::DrawFromDeck => {
+                if self.deck.empty() {
+                    Err(MoveError::NotEnoughCards(Deck))
+                } else {
+                    Ok(())
+                }
+            }
+            Move::RecycleDiscard => {
+                if !self.deck.empty() {
+                    Err(MoveError::DeckNotEmpty)
+                } else if self.discard.empty() {
+                    Err(MoveError::NotEnoughCards(Discard))
+                } else if self.recyclesLeft() == Some(0) {
+                    Err(MoveError::NoPassesLeft)
+                } else {
+                    Ok(())
+                }
+            }
+            Move::Flip(identifier) => {
+                checkPile(identifier)?;
+                match self.identifyPile(*identifier).getTopCard() {
+                    Some(card) if !card.isFaceUp => Ok(()),
+                    _ => Err(MoveError::NotFaceDown(*identifier)),
+                }
+            }
+            Move::TableauToTableau { from, to, count } => {
+                checkPile(from)?;
+                checkPile(to)?;
+                self.validateTransfer(from, to, *count)
+            }
+            Move::TableauToFoundation { from, to } => {
+                checkPile(from)?;
+                checkFinal(to)?;
+                self.validateTransfer(from, to, 1)
+            }
+            Move::DiscardToTableau { to } => {
+                checkPile(to)?;
+                self.validateTransfer(&Discard, to, 1)
+            }
+            Move::DiscardToFoundation { to } => {
+                checkFinal(to)?;
+                self.validateTransfer(&Discard, to, 1)
+            }
+            Move::FoundationToTableau { from, to } => {
+                checkFinal(from)?;
+                checkPile(to)?;
+                self.validateTransfer(from, to, 1)
+            }
+        }
+    }
+
+    /// Returns a copy of the current position without the history
+    fn snapshot(&mut self) -> Engine {
+        let history = mem::take(&mut self.history);
+        let snapshot = self.clone();
+        self.history = history;
+        snapshot
+    }
+
+    /// Replaces the current position with a snapshot while keeping the history and the clock.
+    /// The move count goes up by one, or is taken from the snapshot if undo does not count as a move.
+    fn restore(&mut self, snapshot: Engine) {
+        let history = mem::take(&mut self.history);
+        let placements: Vec<(PileIdentifier, f32, f32, f32, f32)> = [Deck, Discard]
+            .into_iter()
+            .chain(FINAL_IDENTIFIERS)
+            .chain(PILE_IDENTIFIERS)
+            .map(|identifier| {
+                let pile = self.identifyPile(identifier);
+                (identifier, pile.x, pile.y, pile.faceDownOffset, pile.offset)
+            })
+            .collect();
+        let moves = if self.undoCountsAsMove { self.moves + 1 } else { snapshot.moves };
+        let (elapsed, undoCountsAsMove) = (self.elapsed, self.undoCountsAsMove);
+        *self = snapshot;
+        self.history = history;
+        self.moves = moves;
+        self.elapsed = elapsed;
+        self.undoCountsAsMove = undoCountsAsMove;
+        // The piles stay where they are on the table, which may have changed since the snapshot
+        for (identifier, x, y, faceDownOffset, offset) in placements {
+            self.placePile(identifier, x, y, faceDownOffset, offset);
+        }
+    }
+
+    /// Takes back the last applied move. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let current = self.snapshot();
+        match self.history.undo(current) {
+            Some(previous) => {
+                self.restore(previous);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applies the last undone move again. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let current = self.snapshot();
+        match self.history.redo(current) {
+            Some(next) => {
+                self.restore(next);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Checks if there is a move to undo
+    pub fn canUndo(&self) -> bool {
+        self.history.canUndo()
+    }
+
+    /// Checks if there is a move to redo
+    pub fn canRedo(&self) -> bool {
+        self.history.canRedo()
+    }
+
+    /// Applies a move if it is allowed by the rules and records it in the history
+    pub fn apply(&mut self, action: Move) -> Result<(), MoveError> {
+        self.validate(&action)?;
+        let previous = self.snapshot();
+        self.history.record(previous);
+        self.score = self.scoring.add(self.score, self.scoring.points(&action, self.difficulty));
+        self.moves += 1;
+        match action {
+            Move::DrawFromDeck => self.discardCard(),
+            Move::RecycleDiscard => {
+                self.emptyDiscard();
+                self.recycles += 1;
+            }
+            Move::Flip(identifier) => self.identifyPileMut(identifier).flipTopCard(),
+            _ => {
+                let (from, to, count) = (action.source().unwrap(), action.destination().unwrap(), action.count());
+                let source = self.identifyPileMut(from);
+                let cards = source.cards.split_off(source.size() - count);
+                let receiver = self.identifyPileMut(to);
+                for card in cards {
+                    match to {
+                        Final1 | Final2 | Final3 | Final4 => receiver.addNoOffset(card),
+                        _ => receiver.addCard(card),
+                    }
+                }
+            }
+        }
+        self.checkForWin();
+        Ok(())
+    }
+
+    /// Returns the final pile a card can be moved to
+    pub fn foundationFor(&self, card: &Card) -> Option<PileIdentifier> {
+        FINAL_IDENTIFIERS.into_iter().find(|identifier| self.canBeStacked(card, *identifier))
+    }
+
+    /// Checks if a card can go to its final pile without ever being needed on a pile again:
+    /// both cards of the other color and one rank lower, which it could hold, are already on the final piles
+    pub fn isSafeForFoundation(&self, card: &Card) -> bool {
+        let rank = card.rank as usize;
+        if rank <= Rank::Two as usize {
+            return true;
+        }
+        FINAL_IDENTIFIERS
+            .into_iter()
+            .map(|identifier| self.identifyPile(identifier))
+            .filter(|pile| pile.getTopCard().is_some_and(|top| top.suit.getColor() != card.suit.getColor()))
+            .filter(|pile| pile.size() >= rank)
+            .count()
+            == 2
+    }
+
+    /// Returns a move of a top card of a pile or of the discard pile to its final pile that is safe
+    pub fn nextSafeFoundationMove(&self) -> Option<Move> {
+        PILE_IDENTIFIERS
+            .into_iter()
+            .chain([Discard])
+            .find_map(|from| {
+                let card = self.identifyPile(from).getTopCard()?;
+                if !card.isFaceUp || !self.isSafeForFoundation(card) {
+                    return None;
+                }
+                Move::transfer(from, self.foundationFor(card)?, 1)
+            })
+    }
+
+    /// Checks if the game is mechanically won: the deck and the discard pile are empty
+    /// and every card on the piles is face up, so the cards only need to be moved to the final piles
+    pub fn canAutoComplete(&self) -> bool {
+        !self.gameOver
+            && self.deck.empty()
+            && self.discard.empty()
+            && PILE_IDENTIFIERS
+                .into_iter()
+                .all(|identifier| self.identifyPile(identifier).cards.iter().all(|card| card.isFaceUp))
+    }
+
+    /// Returns the next move of an auto-complete: the lowest top card of a pile goes to its final pile
+    pub fn nextAutoCompleteMove(&self) -> Option<Move> {
+        if !self.canAutoComplete() {
+            return None;
+        }
+        PILE_IDENTIFIERS
+            .into_iter()
+            .filter_map(|from| {
+                let card = self.identifyPile(from).getTopCard()?;
+                let to = self.foundationFor(card)?;
+                Some((card.rank as i32, Move::TableauToFoundation { from, to }))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, action)| action)
+    }
+
+    /// Returns every move that is legal in the current position
+    pub fn legalMoves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        if self.gameOver {
+            return moves;
+        }
+
+        for from in PILE_IDENTIFIERS {
+            let pile = self.identifyPile(from);
+            match pile.getTopCard() {
+                None => continue,
+                Some(card) if !card.isFaceUp => {
+                    moves.push(Move::Flip(from));
+                    continue;
+                }
+                Some(card) => {
+                    for to in FINAL_IDENTIFIERS {
+                        if self.canBeStacked(card, to) {
+                            moves.push(Move::TableauToFoundation { from, to });
+                        }
+                    }
+                }
+            }
+            for (index, card) in pile.cards.iter().enumerate() {
+                if !card.isFaceUp {
+                    continue;
+                }
+                for to in PILE_IDENTIFIERS {
+                    if to != from && self.canBeStacked(card, to) {
+                        let count = pile.size() - index;
+                        moves.push(Move::TableauToTableau { from, to, count });
+                    }
+                }
+            }
+        }
+
+        if let Some(card) = self.discard.getTopCard() {
+            for to in FINAL_IDENTIFIERS {
+                if self.canBeStacked(card, to) {
+                    moves.push(Move::DiscardToFoundation { to });
+                }
+            }
+            for to in PILE_IDENTIFIERS {
+                if self.canBeStacked(card, to) {
+                    moves.push(Move::DiscardToTableau { to });
+                }
+            }
+        }
+
+        for from in FINAL_IDENTIFIERS {
+            if let Some(card) = self.identifyPile(from).getTopCard() {
+                for to in PILE_IDENTIFIERS {
+                    if self.canBeStacked(card, to) {
+                        moves.push(Move::FoundationToTableau { from, to });
+                    }
+                }
+            }
+        }
+
+        if !self.deck.empty() {
+            moves.push(Move::DrawFromDeck);
+        } else if !self.discard.empty() && self.recyclesLeft() != Some(0) {
+            moves.push(Move::RecycleDiscard);
+        }
+
+        moves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::notation::{self, cardCode, parseCard};
+
+    /// Reads a position in which every card that is not on the given piles is face down in the deck
+    fn position(piles: &str) -> Engine {
+        let listed: Vec<String> = piles
+            .split([' ', ':', ','])
+            .map(|word| word.trim_start_matches('#').to_uppercase())
+            .collect();
+        let deck: Vec<String> = SUITS
+            .into_iter()
+            .flat_map(|suit| RANKS.into_iter().map(move |rank| cardCode(&Card::new(suit, rank))))
+            .filter(|code| !listed.contains(&code[1..].to_string()))
+            .collect();
+        notation::parse(&format!("draw1 {} s:{}", piles, deck.join(","))).unwrap()
+    }
+
+    /// Returns the codes of the cards of a pile, from the bottom up
+    fn cards(engine: &Engine, identifier: PileIdentifier) -> Vec<String> {
+        engine.identifyPile(identifier).cards.iter().map(cardCode).collect()
+    }
+
+    #[test]
+    fn movesOnlyAKingToAnEmptyPile() {
+        let mut engine = position("t1:KS t2:QH");
+        let queen = Move::TableauToTableau { from: Pile2, to: Pile3, count: 1 };
+        assert_eq!(engine.validate(&queen), Err(MoveError::CannotBeStacked(Pile3)));
+
+        engine.apply(Move::TableauToTableau { from: Pile1, to: Pile3, count: 1 }).unwrap();
+        assert!(cards(&engine, Pile1).is_empty());
+        assert_eq!(cards(&engine, Pile3), ["KS"]);
+    }
+
+    #[test]
+    fn stacksCardsGoingDownInAlternatingColors() {
+        let mut engine = position("t1:KS t2:QS t3:QH t4:JD");
+        let sameColor = Move::TableauToTableau { from: Pile2, to: Pile1, count: 1 };
+        assert_eq!(engine.validate(&sameColor), Err(MoveError::CannotBeStacked(Pile1)));
+        let sameRank = Move::TableauToTableau { from: Pile3, to: Pile2, count: 1 };
+        assert_eq!(engine.validate(&sameRank), Err(MoveError::CannotBeStacked(Pile2)));
+
+        engine.apply(Move::TableauToTableau { from: Pile3, to: Pile1, count: 1 }).unwrap();
+        engine.apply(Move::TableauToTableau { from: Pile4, to: Pile2, count: 1 }).unwrap();
+        engine.apply(Move::TableauToTableau { from: Pile1, to: Pile3, count: 2 }).unwrap();
+        assert_eq!(cards(&engine, Pile2), ["QS", "JD"]);
+        assert_eq!(cards(&engine, Pile3), ["KS", "QH"]);
+    }
+
+    #[test]
+    fn buildsTheFinalPilesUpBySuitFromTheAce() {
+        let mut engine = position("t1:2H t2:AH t3:3S w:2D");
+        let two = Move::TableauToFoundation { from: Pile1, to: Final1 };
+        assert_eq!(engine.validate(&two), Err(MoveError::CannotBeStacked(Final1)));
+
+        engine.apply(Move::TableauToFoundation { from: Pile2, to: Final1 }).unwrap();
+        let otherSuit = Move::DiscardToFoundation { to: Final1 };
+        assert_eq!(engine.validate(&otherSuit), Err(MoveError::CannotBeStacked(Final1)));
+        engine.apply(two).unwrap();
+        assert_eq!(cards(&engine, Final1), ["AH", "2H"]);
+
+        engine.apply(Move::FoundationToTableau { from: Final1, to: Pile3 }).unwrap();
+        assert_eq!(cards(&engine, Final1), ["AH"]);
+        assert_eq!(cards(&engine, Pile3), ["3S", "2H"]);
+    }
+
+    #[test]
+    fn drawsAndRecyclesTheDeck() {
+        let mut engine = Engine::withSeed(Difficulty::Easy, 1);
+        assert_eq!(engine.validate(&Move::RecycleDiscard), Err(MoveError::DeckNotEmpty));
+        let deck = engine.identifyPile(Deck).size();
+        for _ in 0..deck {
+            engine.apply(Move::DrawFromDeck).unwrap();
+        }
+        assert!(engine.identifyPile(Discard).cards.iter().all(|card| card.isFaceUp));
+        assert_eq!(engine.validate(&Move::DrawFromDeck), Err(MoveError::NotEnoughCards(Deck)));
+
+        engine.apply(Move::RecycleDiscard).unwrap();
+        assert!(engine.identifyPile(Discard).empty());
+        assert_eq!(engine.identifyPile(Deck).size(), deck);
+        assert!(engine.identifyPile(Deck).cards.iter().all(|card| !card.isFaceUp));
+        assert_eq!(engine.recycles(), 1);
+    }
+
+    #[test]
+    fn flipsOnlyAFaceDownTopCard() {
+        let mut engine = position("t1:#KS t2:QH");
+        assert_eq!(engine.validate(&Move::Flip(Pile2)), Err(MoveError::NotFaceDown(Pile2)));
+        assert_eq!(engine.validate(&Move::Flip(Pile3)), Err(MoveError::NotFaceDown(Pile3)));
+        engine.apply(Move::Flip(Pile1)).unwrap();
+        assert_eq!(cards(&engine, Pile1), ["KS"]);
+    }
+
+    #[test]
+    fn rejectsIllegalMovesWithAnErrorAndLeavesThePositionAlone() {
+        let mut engine = Engine::withSeed(Difficulty::Easy, 42);
+        let dealt = notation::format(&engine);
+        for (action, error) in [
+            (Move::TableauToTableau { from: Pile1, to: Pile1, count: 1 }, MoveError::InvalidPile(Pile1)),
+            (Move::TableauToTableau { from: Pile1, to: Pile2, count: 0 }, MoveError::NotEnoughCards(Pile1)),
+            (Move::TableauToTableau { from: Pile1, to: Pile2, count: 2 }, MoveError::NotEnoughCards(Pile1)),
+            (Move::TableauToTableau { from: Pile7, to: Pile1, count: 2 }, MoveError::FaceDown(Pile7)),
+            (Move::TableauToTableau { from: Deck, to: Pile1, count: 1 }, MoveError::InvalidPile(Deck)),
+            (Move::TableauToFoundation { from: Pile1, to: Pile2 }, MoveError::InvalidPile(Pile2)),
+            (Move::DiscardToTableau { to: Pile1 }, MoveError::NotEnoughCards(Discard)),
+            (Move::DiscardToFoundation { to: GrabbedCards }, MoveError::InvalidPile(GrabbedCards)),
+            (Move::FoundationToTableau { from: Final1, to: Pile1 }, MoveError::NotEnoughCards(Final1)),
+            (Move::Flip(Deck), MoveError::InvalidPile(Deck)),
+        ] {
+            assert_eq!(engine.apply(action.clone()), Err(error), "{:?}", action);
+        }
+        assert_eq!(notation::format(&engine), dealt);
+        assert_eq!(engine.moves(), 0);
+        assert!(!engine.canUndo());
+    }
+
+    #[test]
+    fn rejectsEveryMoveOnceTheGameIsWon() {
+        let engine = notation::parse(&format!(
+            "draw1 f1:{} f2:{} f3:{} f4:{}",
+            "AH,2H,3H,4H,5H,6H,7H,8H,9H,TH,JH,QH,KH",
+            "AD,2D,3D,4D,5D,6D,7D,8D,9D,TD,JD,QD,KD",
+            "AC,2C,3C,4C,5C,6C,7C,8C,9C,TC,JC,QC,KC",
+            "AS,2S,3S,4S,5S,6S,7S,8S,9S,TS,JS,QS,KS"
+        ))
+        .unwrap();
+        assert!(engine.isGameOver());
+        assert_eq!(engine.validate(&Move::FoundationToTableau { from: Final4, to: Pile1 }), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn sendsACardToItsFinalPileWhenNoCardCouldNeedItAnyMore() {
+        let engine = position("f1:AH,2H f2:AD f3:AC,2C f4:AS,2S t1:3H t2:3C t3:2D");
+        assert!(engine.isSafeForFoundation(&parseCard("3H").unwrap()));
+        assert!(!engine.isSafeForFoundation(&parseCard("3C").unwrap()));
+        assert!(engine.isSafeForFoundation(&parseCard("2D").unwrap()));
+        assert_eq!(
+            engine.nextSafeFoundationMove(),
+            Some(Move::TableauToFoundation { from: Pile1, to: Final1 })
+        );
+    }
+
+    #[test]
+    fn listsOnlyLegalMoves() {
+        for seed in 0..20 {
+            let mut engine = Engine::withSeed(Difficulty::Hard, seed);
+            for _ in 0..5 {
+                let moves = engine.legalMoves();
+                for action in &moves {
+                    assert_eq!(engine.validate(action), Ok(()), "{:?} in deal {}", action, seed);
+                }
+                engine.apply(moves.last().unwrap().clone()).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn listsEveryLegalMove() {
+        let engine = position("w:JC f1:AH t1:KS t2:QH t3:#5C,JD t4:9S t5:2C t6:8H t7:#TC");
+        let expected = vec![
+            Move::TableauToTableau { from: Pile2, to: Pile1, count: 1 },
+            Move::TableauToTableau { from: Pile6, to: Pile4, count: 1 },
+            Move::Flip(Pile7),
+            Move::DiscardToTableau { to: Pile2 },
+            Move::FoundationToTableau { from: Final1, to: Pile5 },
+            Move::DrawFromDeck,
+        ];
+        assert_eq!(engine.legalMoves(), expected);
+    }
+
+    #[test]
+    fn countsUndoAsAMoveOnlyWhenAsked() {
+        for (undoCountsAsMove, afterUndo, afterRedo) in [(true, 3, 4), (false, 1, 2)] {
+            let mut engine = Engine::withSeed(Difficulty::Easy, 42);
+            engine.setUndoCountsAsMove(undoCountsAsMove);
+            engine.apply(Move::DrawFromDeck).unwrap();
+            engine.apply(Move::DrawFromDeck).unwrap();
+            assert!(engine.undo());
+            assert_eq!(engine.moves(), afterUndo, "{}", undoCountsAsMove);
+            assert!(engine.redo());
+            assert_eq!(engine.moves(), afterRedo, "{}", undoCountsAsMove);
+        }
+    }
+
+    #[test]
+    fn autoCompletesAGameWithEveryCardFaceUp() {
+        let finals = "f1:AH,2H,3H,4H,5H,6H,7H,8H,9H f2:AD,2D,3D,4D,5D,6D,7D,8D,9D \
+                      f3:AC,2C,3C,4C,5C,6C,7C,8C,9C,TC f4:AS,2S,3S,4S,5S,6S,7S,8S,9S,TS";
+        let piles = "t1:KC,QH,JS,TH t2:KS,QD,JC,TD t4:KD,QC,JH";
+        let blocked = position(&format!("{} {} t3:#KH,QS,JD", finals, piles));
+        assert!(!blocked.canAutoComplete());
+        assert_eq!(blocked.nextAutoCompleteMove(), None);
+
+        let mut engine = position(&format!("{} {} t3:KH,QS,JD", finals, piles));
+        assert!(engine.canAutoComplete());
+        while let Some(action) = engine.nextAutoCompleteMove() {
+            engine.apply(action).unwrap();
+        }
+        assert!(engine.isGameOver());
+        assert_eq!(engine.moves(), 14);
+        assert!(!engine.canAutoComplete());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::card::pile::identifier::PileIdentifier;
+use crate::engine::notation::pileName;
+use std::fmt;
+
+#[derive(Clone, PartialEq, Debug)]
+/// The reason a move was rejected by the engine
+pub enum MoveError {
+    /// The game is already won
+    GameOver,
+    /// The discard pile can only be recycled once the deck is empty
+    DeckNotEmpty,
+    /// The scoring rules allow no more passes through the deck
+    NoPassesLeft,
+    /// The pile does not belong to the engine or cannot be used for this move
+    InvalidPile(PileIdentifier),
+    /// The pile has fewer cards than the move requires
+    NotEnoughCards(PileIdentifier),
+    /// Only the top face-down card of a pile can be flipped
+    NotFaceDown(PileIdentifier),
+    /// Only face-up cards can be moved
+    FaceDown(PileIdentifier),
+    /// The cards cannot be stacked on the destination pile
+    CannotBeStacked(PileIdentifier),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::GameOver => write!(f, "the game is over"),
+            MoveError::DeckNotEmpty => write!(f, "the deck is not empty yet"),
+            MoveError::NoPassesLeft => write!(f, "no more passes through the deck are allowed"),
+            MoveError::InvalidPile(pile) => write!(f, "{} cannot be used for this move", pileName(*pile)),
+            MoveError::NotEnoughCards(pile) => write!(f, "{} does not have enough cards", pileName(*pile)),
+            MoveError::NotFaceDown(pile) => write!(f, "the top card of {} is not face-down", pileName(*pile)),
+            MoveError::FaceDown(pile) => write!(f, "{} has face-down cards in the way", pileName(*pile)),
+            MoveError::CannotBeStacked(pile) => write!(f, "the cards cannot be stacked on {}", pileName(*pile)),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+#[derive(Clone, PartialEq, Debug)]
+/// The reason a position could not be loaded into the engine
+pub enum PositionError {
+    /// The pile does not belong to the engine or appears more than once
+    InvalidPile(PileIdentifier),
+    /// The position does not contain every card of the deck exactly once
+    InvalidCards,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::InvalidPile(pile) => write!(f, "{} is invalid or appears more than once", pileName(*pile)),
+            PositionError::InvalidCards => write!(f, "the position does not contain all 52 cards exactly once"),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
@@ -0,0 +1,98 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+
+#[derive(Clone, Debug, Default)]
+/// The positions before (undo) and after (redo) every applied move
+pub struct History {
+    /// The positions that undo returns to, the most recent one last
+    undoStack: Vec<Engine>,
+    /// The positions that redo returns to, the most recent one last
+    redoStack: Vec<Engine>,
+}
+
+impl History {
+    /// Records the position before a new move. A new move discards everything that could be redone.
+    pub fn record(&mut self, position: Engine) {
+        self.undoStack.push(position);
+        self.redoStack.clear();
+    }
+
+    /// Swaps the current position with the one before the last move
+    pub fn undo(&mut self, current: Engine) -> Option<Engine> {
+        let previous = self.undoStack.pop()?;
+        self.redoStack.push(current);
+        Some(previous)
+    }
+
+    /// Swaps the current position with the one after the last undone move
+    pub fn redo(&mut self, current: Engine) -> Option<Engine> {
+        let next = self.redoStack.pop()?;
+        self.undoStack.push(current);
+        Some(next)
+    }
+
+    /// Checks if there is a move to undo
+    pub fn canUndo(&self) -> bool {
+        !self.undoStack.is_empty()
+    }
+
+    /// Checks if there is a move to redo
+    pub fn canRedo(&self) -> bool {
+        !self.redoStack.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::pile::identifier::PileIdentifier::*;
+    use crate::engine::engine::Engine;
+    use crate::engine::moves::Move;
+    use crate::engine::notation;
+    use crate::game::difficulty::Difficulty;
+
+    /// Returns what undo must bring back: the piles, the score and the move count
+    fn state(engine: &Engine) -> (String, i32, u32) {
+        (notation::format(engine), engine.score(), engine.moves())
+    }
+
+    #[test]
+    fn undoesAndRedoesMovesExactly() {
+        // Deal 2 has the Ace of Diamonds face up on the seventh pile
+        let mut engine = Engine::withSeed(Difficulty::Easy, 2);
+        engine.setUndoCountsAsMove(false);
+        let mut states = vec![state(&engine)];
+        for action in [Move::TableauToFoundation { from: Pile7, to: Final1 }, Move::Flip(Pile7), Move::DrawFromDeck] {
+            engine.apply(action).unwrap();
+            states.push(state(&engine));
+        }
+        assert_eq!(engine.score(), 15);
+
+        for previous in states[..3].iter().rev() {
+            assert!(engine.undo());
+            assert_eq!(state(&engine), *previous);
+        }
+        assert!(!engine.undo());
+        for next in &states[1..] {
+            assert!(engine.redo());
+            assert_eq!(state(&engine), *next);
+        }
+        assert!(!engine.redo());
+    }
+
+    #[test]
+    fn forgetsTheUndoneMovesAfterANewMove() {
+        let mut engine = Engine::withSeed(Difficulty::Easy, 42);
+        engine.apply(Move::DrawFromDeck).unwrap();
+        engine.apply(Move::DrawFromDeck).unwrap();
+        assert!(engine.undo());
+        assert!(engine.canRedo());
+
+        engine.apply(Move::DrawFromDeck).unwrap();
+        assert!(!engine.canRedo());
+        assert!(!engine.redo());
+        assert!(engine.undo());
+        assert!(engine.undo());
+        assert!(!engine.canUndo());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod engine;
+pub mod error;
+pub mod history;
+pub mod moves;
+pub mod notation;
+pub mod save;
+pub mod scoring;
+pub mod shuffle;
@@ -0,0 +1,86 @@
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::Discard;
+
+#[derive(Clone, PartialEq, Debug)]
+/// An action that can be applied to the engine
+pub enum Move {
+    /// Moves the top card(s) of the deck to the discard pile (1 on Easy, up to 3 on Hard)
+    DrawFromDeck,
+    /// Returns the discard pile to the empty deck
+    RecycleDiscard,
+    /// Moves the top `count` cards of a pile onto another pile
+    TableauToTableau {
+        from: PileIdentifier,
+        to: PileIdentifier,
+        count: usize,
+    },
+    /// Moves the top card of a pile to a final pile
+    TableauToFoundation {
+        from: PileIdentifier,
+        to: PileIdentifier,
+    },
+    /// Moves the top card of the discard pile onto a pile
+    DiscardToTableau { to: PileIdentifier },
+    /// Moves the top card of the discard pile to a final pile
+    DiscardToFoundation { to: PileIdentifier },
+    /// Moves the top card of a final pile back onto a pile
+    FoundationToTableau {
+        from: PileIdentifier,
+        to: PileIdentifier,
+    },
+    /// Flips the face-down top card of a pile
+    Flip(PileIdentifier),
+}
+
+impl Move {
+    /// Creates the move that takes the top `count` cards of `from` to `to`, if such a move exists
+    pub fn transfer(from: PileIdentifier, to: PileIdentifier, count: usize) -> Option<Move> {
+        if from.isPile() && to.isPile() {
+            Some(Move::TableauToTableau { from, to, count })
+        } else if count != 1 {
+            None
+        } else if from.isPile() && to.isFinal() {
+            Some(Move::TableauToFoundation { from, to })
+        } else if from == Discard && to.isPile() {
+            Some(Move::DiscardToTableau { to })
+        } else if from == Discard && to.isFinal() {
+            Some(Move::DiscardToFoundation { to })
+        } else if from.isFinal() && to.isPile() {
+            Some(Move::FoundationToTableau { from, to })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the pile the cards are taken from (None for deck actions and flips)
+    pub fn source(&self) -> Option<PileIdentifier> {
+        match self {
+            Move::TableauToTableau { from, .. }
+            | Move::TableauToFoundation { from, .. }
+            | Move::FoundationToTableau { from, .. } => Some(*from),
+            Move::DiscardToTableau { .. } | Move::DiscardToFoundation { .. } => Some(Discard),
+            Move::DrawFromDeck | Move::RecycleDiscard | Move::Flip(_) => None,
+        }
+    }
+
+    /// Returns the pile the cards are placed on (None for deck actions and flips)
+    pub fn destination(&self) -> Option<PileIdentifier> {
+        match self {
+            Move::TableauToTableau { to, .. }
+            | Move::TableauToFoundation { to, .. }
+            | Move::DiscardToTableau { to }
+            | Move::DiscardToFoundation { to }
+            | Move::FoundationToTableau { to, .. } => Some(*to),
+            Move::DrawFromDeck | Move::RecycleDiscard | Move::Flip(_) => None,
+        }
+    }
+
+    /// Returns the number of cards moved between the source and the destination
+    pub fn count(&self) -> usize {
+        match self {
+            Move::TableauToTableau { count, .. } => *count,
+            Move::DrawFromDeck | Move::RecycleDiscard | Move::Flip(_) => 0,
+            _ => 1,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+#![allow(non_snake_case)]
+
+use crate::card::card::Card;
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::rank::RANKS;
+use crate::card::suit::SUITS;
+use crate::engine::engine::Engine;
+use crate::engine::error::PositionError;
+use crate::game::difficulty::Difficulty;
+use std::fmt;
+
+/// The piles in the order they are written, with their names
+const NAMED_PILES: [(PileIdentifier, &str); 13] = [
+    (Deck, "s"),
+    (Discard, "w"),
+    (Final1, "f1"),
+    (Final2, "f2"),
+    (Final3, "f3"),
+    (Final4, "f4"),
+    (Pile1, "t1"),
+    (Pile2, "t2"),
+    (Pile3, "t3"),
+    (Pile4, "t4"),
+    (Pile5, "t5"),
+    (Pile6, "t6"),
+    (Pile7, "t7"),
+];
+/// The code of every rank, from Ace to King
+const RANK_CODES: [char; 13] = ['A', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K'];
+/// The code of every suit, in the order of `SUITS`
+const SUIT_CODES: [char; 4] = ['H', 'D', 'C', 'S'];
+/// Written before the code of a face-down card
+const FACE_DOWN: char = '#';
+/// The words that give the number of cards drawn at once
+const DRAW_MODES: [(Difficulty, &str); 2] = [(Difficulty::Easy, "draw1"), (Difficulty::Hard, "draw3")];
+
+#[derive(Clone, PartialEq, Debug)]
+/// The reason a position could not be read
+pub enum NotationError {
+    /// The position does not start with draw1 or draw3
+    MissingDrawMode,
+    /// The word is not the name of a pile followed by a colon and its cards
+    InvalidPile(String),
+    /// The code is not a card
+    InvalidCard(String),
+    /// The cards of the pile cannot be in this order or with this side up
+    Misplaced(PileIdentifier),
+    /// The piles do not make up a game
+    Position(PositionError),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::MissingDrawMode => write!(f, "the position does not start with draw1 or draw3"),
+            NotationError::InvalidPile(word) => write!(f, "'{}' is not a pile followed by its cards", word),
+            NotationError::InvalidCard(code) => write!(f, "'{}' is not a card", code),
+            NotationError::Misplaced(pile) => {
+                write!(f, "the cards of {} cannot lie like this in a game", pileName(*pile))
+            }
+            NotationError::Position(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Returns the name of a pile: s (the deck, or stock), w (the discard pile, or waste), f1 to f4 and t1 to t7
+/// (the cards being dragged, which are not part of a position, have no short name)
+pub fn pileName(identifier: PileIdentifier) -> &'static str {
+    NAMED_PILES
+        .iter()
+        .find(|(pile, _)| *pile == identifier)
+        .map_or("the grabbed cards", |(_, name)| name)
+}
+
+/// Returns the pile with the given name, in upper or lower case
+pub fn pileByName(name: &str) -> Option<PileIdentifier> {
+    NAMED_PILES
+        .iter()
+        .find(|(_, pileName)| pileName.eq_ignore_ascii_case(name))
+        .map(|(pile, _)| *pile)
+}
+
+/// Returns the code of a card: its rank and suit (e.g. AH, TD or KS), after a # if it is face down
+pub fn cardCode(card: &Card) -> String {
+    let code = format!("{}{}", RANK_CODES[card.rank as usize], SUIT_CODES[card.suit as usize]);
+    if card.isFaceUp {
+        code
+    } else {
+        format!("{}{}", FACE_DOWN, code)
+    }
+}
+
+/// Writes the position on one line: the number of cards drawn at once, then every pile by name
+/// with its cards from the bottom up, e.g. `draw1 s:#8D,#3C w:QH f1:AS f2: f3: f4: t1:KS t2:#9C,5H ...`
+pub fn format(engine: &Engine) -> String {
+    let draw = DRAW_MODES
+        .iter()
+        .find(|(difficulty, _)| *difficulty == engine.difficulty())
+        .map_or("?", |(_, word)| word);
+    let piles = NAMED_PILES.iter().map(|(identifier, name)| {
+        let cards: Vec<String> = engine.identifyPile(*identifier).cards.iter().map(cardCode).collect();
+        format!("{}:{}", name, cards.join(","))
+    });
+    std::iter::once(draw.to_string()).chain(piles).collect::<Vec<String>>().join(" ")
+}
+
+/// Reads the card with the given code, in upper or lower case
+pub fn parseCard(code: &str) -> Result<Card, NotationError> {
+    let invalid = || NotationError::InvalidCard(code.to_string());
+    let (isFaceUp, face) = match code.strip_prefix(FACE_DOWN) {
+        Some(face) => (false, face),
+        None => (true, code),
+    };
+    let mut characters = face.chars().map(|character| character.to_ascii_uppercase());
+    let (Some(rank), Some(suit), None) = (characters.next(), characters.next(), characters.next()) else {
+        return Err(invalid());
+    };
+    let rank = RANK_CODES.iter().position(|code| *code == rank).ok_or_else(invalid)?;
+    let suit = SUIT_CODES.iter().position(|code| *code == suit).ok_or_else(invalid)?;
+    let mut card = Card::new(SUITS[suit], RANKS[rank]);
+    card.isFaceUp = isFaceUp;
+    Ok(card)
+}
+
+/// Reads a position written by `format`. The words may be separated by any white space (e.g. one pile per line),
+/// the piles may come in any order and a pile that is not listed is empty.
+/// The position has no deal number, since it does not come from a shuffled deck.
+pub fn parse(text: &str) -> Result<Engine, NotationError> {
+    let mut words = text.split_whitespace();
+    let difficulty = words
+        .next()
+        .and_then(|word| DRAW_MODES.iter().find(|(_, mode)| mode.eq_ignore_ascii_case(word)))
+        .map(|(difficulty, _)| *difficulty)
+        .ok_or(NotationError::MissingDrawMode)?;
+
+    let mut piles = Vec::new();
+    for word in words {
+        let (name, codes) = word.split_once(':').ok_or_else(|| NotationError::InvalidPile(word.to_string()))?;
+        let identifier = pileByName(name).ok_or_else(|| NotationError::InvalidPile(word.to_string()))?;
+        let cards = codes
+            .split(',')
+            .filter(|code| !code.is_empty())
+            .map(parseCard)
+            .collect::<Result<Vec<Card>, NotationError>>()?;
+        if !Engine::isPlayable(identifier, &cards) {
+            return Err(NotationError::Misplaced(identifier));
+        }
+        piles.push((identifier, cards));
+    }
+
+    Engine::fromPiles(difficulty, None, piles).map_err(NotationError::Position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::moves::Move;
+
+    #[test]
+    fn readsWhatItWrites() {
+        for (difficulty, seed) in [(Difficulty::Easy, 0), (Difficulty::Hard, 42), (Difficulty::Easy, u64::MAX)] {
+            let mut engine = Engine::withSeed(difficulty, seed);
+            let dealt = format(&engine);
+            assert_eq!(format(&parse(&dealt).unwrap()), dealt);
+
+            engine.apply(Move::DrawFromDeck).unwrap();
+            let drawn = format(&engine);
+            let parsed = parse(&drawn).unwrap();
+            assert_eq!(format(&parsed), drawn);
+            assert_eq!(parsed.difficulty(), difficulty);
+            assert_eq!(parsed.seed(), None);
+        }
+    }
+
+    #[test]
+    fn readsPilesOnSeveralLinesInAnyOrderAndCase() {
+        let text = format(&Engine::withSeed(Difficulty::Easy, 9));
+        let mut words: Vec<&str> = text.split(' ').collect();
+        words[1..].reverse();
+        let shuffled = words.join("\n").to_lowercase();
+        assert_eq!(format(&parse(&shuffled).unwrap()), text);
+    }
+
+    #[test]
+    fn rejectsAMissingDrawMode() {
+        assert_eq!(parse("").unwrap_err(), NotationError::MissingDrawMode);
+        assert_eq!(parse("draw2 t1:KS").unwrap_err(), NotationError::MissingDrawMode);
+        assert_eq!(parse("t1:KS").unwrap_err(), NotationError::MissingDrawMode);
+    }
+
+    #[test]
+    fn rejectsInvalidPiles() {
+        assert_eq!(parse("draw1 t8:KS").unwrap_err(), NotationError::InvalidPile("t8:KS".to_string()));
+        assert_eq!(parse("draw1 t1").unwrap_err(), NotationError::InvalidPile("t1".to_string()));
+    }
+
+    #[test]
+    fn rejectsInvalidCards() {
+        for code in ["KX", "1S", "K", "KSS", "##KS"] {
+            let text = format!("draw1 t1:{}", code);
+            assert_eq!(parse(&text).unwrap_err(), NotationError::InvalidCard(code.to_string()));
+        }
+    }
+
+    #[test]
+    fn rejectsMisplacedCards() {
+        for (text, pile) in [
+            ("draw1 s:AH", Deck),
+            ("draw1 w:#AH", Discard),
+            ("draw1 f1:2H", Final1),
+            ("draw1 f2:AH,2D", Final2),
+            ("draw1 t1:KS,#QH", Pile1),
+            ("draw1 t1:KS,2H", Pile1),
+            ("draw1 t7:#AC,KS,QS", Pile7),
+        ] {
+            assert_eq!(parse(text).unwrap_err(), NotationError::Misplaced(pile), "{}", text);
+        }
+    }
+
+    #[test]
+    fn rejectsPositionsThatAreNotAGame() {
+        assert!(matches!(parse("draw1 t1:KS"), Err(NotationError::Position(_))));
+        let twice = format!("{} t1:KH", format(&Engine::withSeed(Difficulty::Easy, 0)));
+        assert!(matches!(parse(&twice), Err(NotationError::Position(_))));
+    }
+}
@@ -0,0 +1,264 @@
+#![allow(non_snake_case)]
+
+use crate::card::card::Card;
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::rank::Rank;
+use crate::card::suit::Suit;
+use crate::engine::engine::Engine;
+use crate::engine::notation::pileName;
+use crate::engine::scoring::Scoring;
+use crate::engine::shuffle::SHUFFLE_VERSION;
+use crate::game::difficulty::Difficulty;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use std::{fmt, fs, io};
+
+/// The version of the save file format. It must be bumped whenever the format changes.
+pub const SAVE_VERSION: u32 = 4;
+/// The oldest save file format that can still be read
+/// (version 1 has no score and version 2 no move count and clock, which then start at 0)
+const OLDEST_SAVE_VERSION: u32 = 1;
+
+/// The piles in the order they are written to the save file
+const SAVED_PILES: [PileIdentifier; 13] = [
+    Deck, Discard, Final1, Final2, Final3, Final4, Pile1, Pile2, Pile3, Pile4, Pile5, Pile6, Pile7,
+];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+/// A card as it is written to the save file
+pub struct SavedCard {
+    pub suit: Suit,
+    pub rank: Rank,
+    pub isFaceUp: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+/// A pile as it is written to the save file (bottom card first)
+pub struct SavedPile {
+    pub identifier: PileIdentifier,
+    pub cards: Vec<SavedCard>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+/// A game in progress as it is written to disk
+pub struct SaveFile {
+    /// The version of the save file format
+    pub version: u32,
+    /// The version of the shuffle algorithm the seed belongs to. The seed is dropped when the game is loaded
+    /// by a version that shuffles differently, since it would no longer reproduce the deal.
+    pub shuffleVersion: u32,
+    pub difficulty: Difficulty,
+    /// The deal number, or null for a position that was not dealt (written as a number before version 4)
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub scoring: Scoring,
+    #[serde(default)]
+    pub score: i32,
+    /// The number of times the discard pile was turned over into the deck
+    #[serde(default)]
+    pub recycles: u32,
+    #[serde(default)]
+    pub moves: u32,
+    /// The time spent playing, in milliseconds
+    #[serde(default)]
+    pub elapsedMillis: u64,
+    pub piles: Vec<SavedPile>,
+}
+
+#[derive(Deserialize)]
+/// The part of the save file that is read before the rest to check the version
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Debug)]
+/// The reason a save file could not be read or written
+pub enum SaveError {
+    /// The file could not be read or written
+    Io(io::Error),
+    /// The file was written by an incompatible version of the game
+    IncompatibleVersion(u32),
+    /// The file is damaged or does not describe a valid game
+    Corrupt(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "{}", error),
+            SaveError::IncompatibleVersion(version) => write!(
+                f,
+                "the save file has version {} but this game only reads versions {} to {}",
+                version, OLDEST_SAVE_VERSION, SAVE_VERSION
+            ),
+            SaveError::Corrupt(reason) => write!(f, "the save file is corrupt: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl SaveFile {
+    /// Captures the current position of the engine
+    pub fn fromEngine(engine: &Engine) -> SaveFile {
+        let piles = SAVED_PILES
+            .into_iter()
+            .map(|identifier| SavedPile {
+                cards: engine
+                    .identifyPile(identifier)
+                    .cards
+                    .iter()
+                    .map(|card| SavedCard {
+                        suit: card.suit,
+                        rank: card.rank,
+                        isFaceUp: card.isFaceUp,
+                    })
+                    .collect(),
+                identifier,
+            })
+            .collect();
+
+        SaveFile {
+            version: SAVE_VERSION,
+            shuffleVersion: SHUFFLE_VERSION,
+            difficulty: engine.difficulty(),
+            seed: engine.seed(),
+            scoring: engine.scoring(),
+            score: engine.score(),
+            recycles: engine.recycles(),
+            moves: engine.moves(),
+            elapsedMillis: engine.elapsed().as_millis() as u64,
+            piles,
+        }
+    }
+
+    /// Rebuilds the engine from the saved position, which must be one a game can reach.
+    /// A game dealt by another shuffle algorithm is continued without a deal number.
+    pub fn intoEngine(self) -> Result<Engine, SaveError> {
+        let seed = self.seed.filter(|_| self.shuffleVersion == SHUFFLE_VERSION);
+        let piles = self
+            .piles
+            .into_iter()
+            .map(|pile| {
+                let cards = pile
+                    .cards
+                    .into_iter()
+                    .map(|saved| {
+                        let mut card = Card::new(saved.suit, saved.rank);
+                        card.isFaceUp = saved.isFaceUp;
+                        card
+                    })
+                    .collect();
+                (pile.identifier, cards)
+            })
+            .collect::<Vec<(PileIdentifier, Vec<Card>)>>();
+        let misplaced = piles.iter().find(|(identifier, cards)| !Engine::isPlayable(*identifier, cards));
+        if let Some((identifier, _)) = misplaced {
+            let reason = format!("the cards of {} cannot lie like this in a game", pileName(*identifier));
+            return Err(SaveError::Corrupt(reason));
+        }
+
+        let mut engine = Engine::fromPiles(self.difficulty, seed, piles)
+            .map_err(|error| SaveError::Corrupt(error.to_string()))?;
+        let elapsed = Duration::from_millis(self.elapsedMillis);
+        engine.restoreProgress(self.scoring, self.score, self.recycles, self.moves, elapsed);
+        Ok(engine)
+    }
+
+    /// Parses a save file, checking its version first
+    pub fn fromJson(json: &str) -> Result<SaveFile, SaveError> {
+        let header: SaveHeader =
+            serde_json::from_str(json).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+        if !(OLDEST_SAVE_VERSION..=SAVE_VERSION).contains(&header.version) {
+            return Err(SaveError::IncompatibleVersion(header.version));
+        }
+        serde_json::from_str(json).map_err(|error| SaveError::Corrupt(error.to_string()))
+    }
+
+    /// Serializes the save file
+    pub fn toJson(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a save file can always be serialized")
+    }
+
+    /// Reads a save file from the given path
+    pub fn read(path: &Path) -> Result<SaveFile, SaveError> {
+        Self::fromJson(&fs::read_to_string(path)?)
+    }
+
+    /// Writes the save file to the given path, creating the directory if needed
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, self.toJson())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::moves::Move;
+    use crate::engine::notation;
+
+    /// Returns a game of deal 7 with a few moves played
+    fn game() -> Engine {
+        let mut engine = Engine::withScoring(Difficulty::Hard, Scoring::Vegas, 7);
+        engine.apply(Move::DrawFromDeck).unwrap();
+        engine.apply(Move::DrawFromDeck).unwrap();
+        engine.tick(Duration::from_millis(83_250));
+        engine
+    }
+
+    #[test]
+    fn restoresTheSavedGame() {
+        let engine = game();
+        let json = SaveFile::fromEngine(&engine).toJson();
+        let restored = SaveFile::fromJson(&json).unwrap().intoEngine().unwrap();
+
+        assert_eq!(notation::format(&restored), notation::format(&engine));
+        assert_eq!(restored.seed(), Some(7));
+        assert_eq!(restored.difficulty(), Difficulty::Hard);
+        assert_eq!(restored.scoring(), Scoring::Vegas);
+        assert_eq!(restored.score(), engine.score());
+        assert_eq!(restored.moves(), 2);
+        assert_eq!(restored.elapsed(), Duration::from_millis(83_250));
+    }
+
+    #[test]
+    fn rejectsUnknownVersions() {
+        for version in [0, SAVE_VERSION + 1] {
+            let mut save = SaveFile::fromEngine(&game());
+            save.version = version;
+            match SaveFile::fromJson(&save.toJson()) {
+                Err(SaveError::IncompatibleVersion(found)) => assert_eq!(found, version),
+                other => panic!("version {} was read: {:?}", version, other),
+            }
+        }
+    }
+
+    #[test]
+    fn dropsTheSeedOfAnotherShuffle() {
+        let mut save = SaveFile::fromEngine(&game());
+        save.shuffleVersion = SHUFFLE_VERSION + 1;
+        assert_eq!(save.intoEngine().unwrap().seed(), None);
+    }
+
+    #[test]
+    fn rejectsPositionsThatAGameCannotReach() {
+        for (identifier, index) in [(Deck, 0), (Pile7, 0)] {
+            let mut save = SaveFile::fromEngine(&game());
+            let pile = save.piles.iter_mut().find(|pile| pile.identifier == identifier).unwrap();
+            pile.cards[index].isFaceUp = true;
+            assert!(matches!(save.intoEngine(), Err(SaveError::Corrupt(_))), "{:?}", identifier);
+        }
+    }
+}
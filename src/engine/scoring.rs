@@ -0,0 +1,163 @@
+#![allow(non_snake_case)]
+
+use crate::engine::moves::Move;
+use crate::game::difficulty::Difficulty;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// The Vegas buy-in, paid when a game starts
+const VEGAS_BUY_IN: i32 = -52;
+/// Games that are won faster than this get no Standard time bonus
+const MIN_BONUS_SECONDS: u64 = 30;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+/// The rule set the score is kept with
+pub enum Scoring {
+    /// Windows-style points for moves, penalties for recycling the deck and a time bonus.
+    /// The score never drops below 0.
+    #[default]
+    Standard,
+    /// A buy-in of 52 and 5 for every card on the final piles, with a limited number of
+    /// passes through the deck (1 when drawing 1 card, 3 when drawing 3)
+    Vegas,
+}
+
+impl Scoring {
+    /// Returns the score at the start of a game
+    pub fn initialScore(self) -> i32 {
+        match self {
+            Scoring::Standard => 0,
+            Scoring::Vegas => VEGAS_BUY_IN,
+        }
+    }
+
+    /// Returns the points a move earns (negative for penalties)
+    pub fn points(self, action: &Move, difficulty: Difficulty) -> i32 {
+        match (self, action) {
+            (Scoring::Standard, Move::TableauToFoundation { .. } | Move::DiscardToFoundation { .. }) => 10,
+            (Scoring::Standard, Move::DiscardToTableau { .. } | Move::Flip(_)) => 5,
+            (Scoring::Standard, Move::FoundationToTableau { .. }) => -15,
+            (Scoring::Standard, Move::RecycleDiscard) => match difficulty {
+                Difficulty::Easy => -100,
+                Difficulty::Hard => -20,
+            },
+            (Scoring::Vegas, Move::TableauToFoundation { .. } | Move::DiscardToFoundation { .. }) => 5,
+            (Scoring::Vegas, Move::FoundationToTableau { .. }) => -5,
+            _ => 0,
+        }
+    }
+
+    /// Adds the points of a move to a score
+    pub fn add(self, score: i32, points: i32) -> i32 {
+        match self {
+            Scoring::Standard => (score + points).max(0),
+            Scoring::Vegas => score + points,
+        }
+    }
+
+    /// Returns how many times the discard pile may be turned over into the deck (None if there is no limit)
+    pub fn maxRecycles(self, difficulty: Difficulty) -> Option<u32> {
+        match (self, difficulty) {
+            (Scoring::Standard, _) => None,
+            (Scoring::Vegas, Difficulty::Easy) => Some(0),
+            (Scoring::Vegas, Difficulty::Hard) => Some(2),
+        }
+    }
+
+    /// Returns the bonus for winning a game in the given time
+    pub fn timeBonus(self, elapsed: Duration) -> i32 {
+        let seconds = elapsed.as_secs();
+        match self {
+            Scoring::Standard if seconds >= MIN_BONUS_SECONDS => (700_000 / seconds) as i32,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Scoring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scoring::Standard => write!(f, "Standard"),
+            Scoring::Vegas => write!(f, "Vegas"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::pile::identifier::PileIdentifier::*;
+    use crate::engine::engine::Engine;
+    use crate::engine::error::MoveError;
+    use Difficulty::{Easy, Hard};
+    use Scoring::{Standard, Vegas};
+
+    #[test]
+    fn scoresEveryMove() {
+        let toFinal = Move::TableauToFoundation { from: Pile1, to: Final1 };
+        let discardToFinal = Move::DiscardToFoundation { to: Final1 };
+        let discardToPile = Move::DiscardToTableau { to: Pile1 };
+        let fromFinal = Move::FoundationToTableau { from: Final1, to: Pile1 };
+        let betweenPiles = Move::TableauToTableau { from: Pile1, to: Pile2, count: 3 };
+        for (scoring, action, difficulty, points) in [
+            (Standard, &toFinal, Easy, 10),
+            (Standard, &discardToFinal, Hard, 10),
+            (Standard, &discardToPile, Easy, 5),
+            (Standard, &Move::Flip(Pile1), Hard, 5),
+            (Standard, &fromFinal, Easy, -15),
+            (Standard, &betweenPiles, Easy, 0),
+            (Standard, &Move::DrawFromDeck, Easy, 0),
+            (Standard, &Move::RecycleDiscard, Easy, -100),
+            (Standard, &Move::RecycleDiscard, Hard, -20),
+            (Vegas, &toFinal, Easy, 5),
+            (Vegas, &discardToFinal, Hard, 5),
+            (Vegas, &discardToPile, Easy, 0),
+            (Vegas, &Move::Flip(Pile1), Easy, 0),
+            (Vegas, &fromFinal, Hard, -5),
+            (Vegas, &Move::RecycleDiscard, Hard, 0),
+        ] {
+            assert_eq!(scoring.points(action, difficulty), points, "{} {:?} {:?}", scoring, action, difficulty);
+        }
+    }
+
+    #[test]
+    fn keepsTheScore() {
+        for (scoring, initial, score, points, total) in [
+            (Standard, 0, 10, -15, 0),
+            (Standard, 0, 120, -100, 20),
+            (Vegas, -52, -52, 5, -47),
+            (Vegas, -52, -47, -5, -52),
+        ] {
+            assert_eq!(scoring.initialScore(), initial, "{}", scoring);
+            assert_eq!(scoring.add(score, points), total, "{} {} {}", scoring, score, points);
+        }
+    }
+
+    #[test]
+    fn limitsThePassesThroughTheDeckOnlyInVegas() {
+        for (scoring, difficulty, maxRecycles) in [
+            (Standard, Easy, None),
+            (Standard, Hard, None),
+            (Vegas, Easy, Some(0)),
+            (Vegas, Hard, Some(2)),
+        ] {
+            assert_eq!(scoring.maxRecycles(difficulty), maxRecycles, "{} {:?}", scoring, difficulty);
+        }
+    }
+
+    #[test]
+    fn stopsRecyclingWhenThePassesRunOut() {
+        for (difficulty, passes) in [(Easy, 0), (Hard, 2)] {
+            let mut engine = Engine::withScoring(difficulty, Vegas, 5);
+            for _ in 0..passes {
+                while engine.apply(Move::DrawFromDeck).is_ok() {}
+                engine.apply(Move::RecycleDiscard).unwrap();
+            }
+            while engine.apply(Move::DrawFromDeck).is_ok() {}
+            assert_eq!(engine.recyclesLeft(), Some(0));
+            assert_eq!(engine.apply(Move::RecycleDiscard), Err(MoveError::NoPassesLeft));
+            assert_eq!(engine.score(), VEGAS_BUY_IN);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+
+/// The version of the shuffle algorithm. It must be bumped whenever the deal produced for a seed changes.
+pub const SHUFFLE_VERSION: u32 = 1;
+
+/// A SplitMix64 generator. It is implemented here instead of using `rand` so that a seed always
+/// produces the same deal, no matter which version of `rand` is used.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Returns the next 64-bit number
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in 0..bound without modulo bias
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
+
+/// Shuffles the items with the Fisher-Yates algorithm, using a generator seeded with `seed`
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut generator = SplitMix64 { state: seed };
+    for i in (1..items.len()).rev() {
+        let j = generator.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::pile::identifier::PileIdentifier::Deck;
+    use crate::card::pile::pile::PILE_IDENTIFIERS;
+    use crate::card::rank::Rank::{self, *};
+    use crate::card::suit::Suit::{self, *};
+    use crate::engine::engine::Engine;
+    use crate::game::difficulty::Difficulty;
+
+    // A change to either of these orders changes every deal: SHUFFLE_VERSION must be bumped with it
+
+    #[test]
+    fn shufflesTheSameWayForASeed() {
+        let mut items: Vec<u8> = (0..52).collect();
+        shuffle(&mut items, 42);
+        let expected: Vec<u8> = vec![
+            6, 28, 12, 39, 40, 0, 26, 25, 22, 4, 46, 36, 27, 42, 31, 17, 14, 43, 18, 13, 19, 1, 33, 7, 16, 32, 51,
+            48, 29, 15, 35, 49, 3, 44, 45, 41, 20, 24, 47, 38, 30, 5, 50, 21, 23, 11, 2, 34, 37, 8, 10, 9,
+        ];
+        assert_eq!(items, expected);
+    }
+
+    #[test]
+    fn dealsTheSameCardsForASeed() {
+        let engine = Engine::withSeed(Difficulty::Easy, 42);
+        let face = |identifier| {
+            let card = engine.identifyPile(identifier).getTopCard().unwrap();
+            (&card.suit, &card.rank)
+        };
+        let tops: Vec<(&Suit, &Rank)> = PILE_IDENTIFIERS.into_iter().map(face).collect();
+        let expected = [
+            (&Heart, &Ten),
+            (&Heart, &Nine),
+            (&Heart, &Three),
+            (&Spade, &Queen),
+            (&Diamond, &Queen),
+            (&Spade, &Jack),
+            (&Diamond, &Four),
+        ];
+        assert_eq!(tops, expected);
+        assert_eq!(face(Deck), (&Heart, &Eight));
+        assert_eq!(engine.identifyPile(Deck).cards.len(), 24);
+    }
+}
@@ -0,0 +1,291 @@
+#![allow(non_snake_case)]
+
+use crate::assets::assets::Assets;
+use crate::card::card::Card;
+use crate::game::layout::Layout;
+use ggez::graphics::{Canvas, Color};
+use rand::Rng;
+
+/// The time in seconds between two cards of the initial deal
+const DEAL_INTERVAL: f32 = 0.04;
+/// The time in seconds between two cards thrown off the final piles when the game is won
+const CASCADE_INTERVAL: f32 = 0.15;
+/// The downward acceleration of the cards of the victory cascade, in pixels per second squared
+const GRAVITY: f32 = 1800.0;
+/// The share of its speed a card of the victory cascade keeps when it bounces off the bottom
+const BOUNCE: f32 = 0.75;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+/// How a tween speeds up and slows down on its way
+pub enum Easing {
+    /// At the same speed all the way
+    Linear,
+    /// Fast at the start, slowing down towards the end
+    #[default]
+    EaseOut,
+    /// Slow at both ends
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the share of the time that has passed (0 to 1) to the share of the way that was covered
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A card moving from where it was seen to where it is in the engine, turning over on the way
+/// if it changed sides
+pub struct Tween {
+    /// The card as it ends up (its coordinates and face are the ones of the engine)
+    card: Card,
+    /// The coordinates the card starts from
+    fromX: f32,
+    fromY: f32,
+    /// The side the card shows at the start
+    fromFaceUp: bool,
+    /// The time in seconds before the card starts moving
+    delay: f32,
+    /// The time in seconds since the card started moving
+    elapsed: f32,
+    /// The time in seconds the card takes to arrive
+    duration: f32,
+    easing: Easing,
+}
+
+impl Tween {
+    /// Returns how far the card got, eased (0 to 1)
+    fn progress(&self) -> f32 {
+        self.easing.apply(self.elapsed / self.duration)
+    }
+
+    /// Checks if the card has arrived
+    fn isDone(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Returns the card where it is seen now, showing the side it currently shows
+    fn current(&self) -> Card {
+        let progress = self.progress();
+        let mut card = self.card;
+        card.x = self.fromX + (self.card.x - self.fromX) * progress;
+        card.y = self.fromY + (self.card.y - self.fromY) * progress;
+        if self.fromFaceUp != self.card.isFaceUp && progress < 0.5 {
+            card.isFaceUp = self.fromFaceUp;
+        }
+        card
+    }
+
+    /// Draws the card, squeezing it while it turns over
+    fn draw(&self, assets: &Assets, canvas: &mut Canvas) {
+        let card = self.current();
+        if self.fromFaceUp == self.card.isFaceUp {
+            assets.drawCard(canvas, &card, Color::WHITE);
+        } else {
+            let scale = (1.0 - 2.0 * self.progress()).abs();
+            assets.drawCardScaled(canvas, &card, scale);
+        }
+    }
+}
+
+/// A card thrown off the final piles when the game is won, bouncing along the bottom of the window
+struct Bouncer {
+    card: Card,
+    /// The speed in pixels per second
+    speedX: f32,
+    speedY: f32,
+}
+
+/// Moves the cards between their places on the table and plays the victory cascade
+pub struct Animator {
+    /// The cards that are on their way, in the order they are drawn
+    tweens: Vec<Tween>,
+    /// The cards of the final piles waiting to be thrown off, the next one last
+    cascadeQueue: Vec<Card>,
+    /// The cards thrown off the final piles so far (they stay hidden from their piles)
+    cascade: Vec<Bouncer>,
+    /// Indicates if the victory cascade was started
+    cascading: bool,
+    /// The time in seconds until the next card of the cascade is thrown off
+    cascadeTimer: f32,
+    /// The time in seconds a card takes to reach its pile (0 turns the animations off)
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Animator {
+    /// Creates an Animator whose cards take `duration` seconds to reach their piles
+    pub fn new(duration: f32, easing: Easing) -> Animator {
+        Animator {
+            tweens: Vec::new(),
+            cascadeQueue: Vec::new(),
+            cascade: Vec::new(),
+            cascading: false,
+            cascadeTimer: 0.0,
+            duration,
+            easing,
+        }
+    }
+
+    /// Checks if the animations are turned on
+    fn enabled(&self) -> bool {
+        self.duration > 0.0
+    }
+
+    /// Checks if some cards are on their way
+    pub fn isBusy(&self) -> bool {
+        !self.tweens.is_empty()
+    }
+
+    /// Checks if the victory cascade was started
+    pub fn isCascading(&self) -> bool {
+        self.cascading
+    }
+
+    /// Checks if a card is drawn by the Animator instead of its pile
+    pub fn isFlying(&self, card: &Card) -> bool {
+        let same = |other: &Card| other.suit == card.suit && other.rank == card.rank;
+        self.tweens.iter().any(|tween| same(&tween.card)) || self.cascade.iter().any(|bouncer| same(&bouncer.card))
+    }
+
+    /// Returns a card where it is seen now if it is on its way
+    pub fn current(&self, card: &Card) -> Option<Card> {
+        self.tweens
+            .iter()
+            .find(|tween| tween.card.suit == card.suit && tween.card.rank == card.rank)
+            .map(Tween::current)
+    }
+
+    /// Adds a card moving from one place to another, replacing the tween the card may already have
+    fn add(&mut self, from: &Card, to: &Card, delay: f32) {
+        self.tweens.retain(|tween| tween.card.suit != to.suit || tween.card.rank != to.rank);
+        self.tweens.push(Tween {
+            card: *to,
+            fromX: from.x,
+            fromY: from.y,
+            fromFaceUp: from.isFaceUp,
+            delay,
+            elapsed: 0.0,
+            duration: self.duration,
+            easing: self.easing,
+        });
+    }
+
+    /// Moves every card from where it was seen (`before`) to where it is now (`after`),
+    /// leaving out the cards that did not change place or side
+    pub fn moveCards(&mut self, before: &[Card], after: &[Card]) {
+        if !self.enabled() {
+            return;
+        }
+        for card in after {
+            let Some(from) = before.iter().find(|from| from.suit == card.suit && from.rank == card.rank) else {
+                continue;
+            };
+            if from.x != card.x || from.y != card.y || from.isFaceUp != card.isFaceUp {
+                self.add(from, card, 0.0);
+            }
+        }
+    }
+
+    /// Deals cards one after the other from a face-down deck at (x, y)
+    pub fn deal(&mut self, x: f32, y: f32, cards: &[Card]) {
+        if !self.enabled() {
+            return;
+        }
+        for (index, card) in cards.iter().enumerate() {
+            let mut from = *card;
+            from.x = x;
+            from.y = y;
+            from.isFaceUp = false;
+            self.add(&from, card, index as f32 * DEAL_INTERVAL);
+        }
+    }
+
+    /// Lets every card on its way arrive at once
+    pub fn skip(&mut self) {
+        self.tweens.clear();
+    }
+
+    /// Starts the victory cascade with the final piles (bottom card first)
+    pub fn startCascade(&mut self, finals: Vec<Vec<Card>>) {
+        self.cascading = true;
+        if !self.enabled() {
+            return;
+        }
+        // Throw the top cards off the piles in turn, so the queue alternates between them
+        let longest = finals.iter().map(Vec::len).max().unwrap_or(0);
+        for index in 0..longest {
+            for pile in finals.iter() {
+                if let Some(card) = pile.get(index) {
+                    self.cascadeQueue.push(*card);
+                }
+            }
+        }
+        self.cascadeTimer = 0.0;
+    }
+
+    /// Stops the victory cascade, putting the cards back on their piles
+    pub fn stopCascade(&mut self) {
+        self.cascading = false;
+        self.cascadeQueue.clear();
+        self.cascade.clear();
+    }
+
+    /// Advances the animations by `seconds`, on the table of a layout
+    pub fn update(&mut self, seconds: f32, layout: &Layout) {
+        for tween in self.tweens.iter_mut() {
+            let late = (seconds - tween.delay).max(0.0);
+            tween.delay = (tween.delay - seconds).max(0.0);
+            tween.elapsed += late;
+        }
+        self.tweens.retain(|tween| !tween.isDone());
+
+        self.cascadeTimer -= seconds;
+        if self.cascadeTimer <= 0.0 {
+            if let Some(card) = self.cascadeQueue.pop() {
+                let mut random = rand::thread_rng();
+                let direction = if random.gen_bool(0.5) { 1.0 } else { -1.0 };
+                self.cascade.push(Bouncer {
+                    card,
+                    speedX: direction * random.gen_range(150.0..400.0),
+                    speedY: -random.gen_range(0.0..500.0),
+                });
+                self.cascadeTimer = CASCADE_INTERVAL;
+            }
+        }
+        let floor = layout.height - layout.cardHeight;
+        for bouncer in self.cascade.iter_mut() {
+            if bouncer.card.x + layout.cardWidth < 0.0 || bouncer.card.x > layout.width {
+                continue;
+            }
+            bouncer.speedY += GRAVITY * seconds;
+            bouncer.card.x += bouncer.speedX * seconds;
+            bouncer.card.y += bouncer.speedY * seconds;
+            if bouncer.card.y > floor {
+                bouncer.card.y = floor;
+                bouncer.speedY = -bouncer.speedY * BOUNCE;
+            }
+        }
+    }
+
+    /// Draws the cards that are on their way and the cards of the victory cascade
+    pub fn draw(&self, assets: &Assets, canvas: &mut Canvas) {
+        for tween in self.tweens.iter() {
+            tween.draw(assets, canvas);
+        }
+        for bouncer in self.cascade.iter() {
+            assets.drawCard(canvas, &bouncer.card, Color::WHITE);
+        }
+    }
+}
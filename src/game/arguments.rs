@@ -0,0 +1,65 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::notation;
+use crate::game::difficulty::Difficulty;
+use crate::game::options::OptionsError;
+use std::iter::Peekable;
+
+/// The arguments of a command line, read one by one, with the values of the options that
+/// the windowed game, the terminal front end and the script driver share
+pub struct Arguments<I: Iterator<Item = String>> {
+    arguments: Peekable<I>,
+}
+
+impl<I: Iterator<Item = String>> Arguments<I> {
+    /// Reads the given arguments (without the program name)
+    pub fn new<A: IntoIterator<Item = String, IntoIter = I>>(arguments: A) -> Arguments<I> {
+        Arguments {
+            arguments: arguments.into_iter().peekable(),
+        }
+    }
+
+    /// Returns the next argument without reading it
+    pub fn peek(&mut self) -> Option<&str> {
+        self.arguments.peek().map(String::as_str)
+    }
+
+    /// Reads the value given after `option`
+    pub fn value(&mut self, option: &str) -> Result<String, OptionsError> {
+        self.arguments
+            .next()
+            .ok_or_else(|| OptionsError::MissingValue(option.to_string()))
+    }
+
+    /// Reads the deal number given after `option` (`--deal <number>`)
+    pub fn deal(&mut self, option: &str) -> Result<u64, OptionsError> {
+        let value = self.value(option)?;
+        value
+            .parse()
+            .map_err(|_| OptionsError::InvalidValue(option.to_string(), value))
+    }
+
+    /// Reads the number of cards drawn at once given after `option` (`--draw 1|3`)
+    pub fn draw(&mut self, option: &str) -> Result<Difficulty, OptionsError> {
+        let value = self.value(option)?;
+        match value.as_str() {
+            "1" => Ok(Difficulty::Easy),
+            "3" => Ok(Difficulty::Hard),
+            _ => Err(OptionsError::InvalidValue(option.to_string(), value)),
+        }
+    }
+
+    /// Reads the position given after `option` in the text notation (`--position <text>`)
+    pub fn position(&mut self, option: &str) -> Result<Engine, OptionsError> {
+        notation::parse(&self.value(option)?).map_err(OptionsError::InvalidPosition)
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for Arguments<I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.arguments.next()
+    }
+}
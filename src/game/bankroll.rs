@@ -0,0 +1,48 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::scoring::Scoring;
+use crate::game::paths;
+use crate::game::preferences::Preferences;
+use serde::{Deserialize, Serialize};
+use std::{fs, io};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+/// The Vegas scores of all finished games added up
+pub struct Bankroll {
+    pub total: i32,
+}
+
+impl Bankroll {
+    /// Reads the bankroll, starting from 0 if there is none or it cannot be read
+    pub fn load() -> Bankroll {
+        paths::bankrollFile()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the bankroll, creating the directory if needed
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = paths::bankrollFile() else {
+            return Ok(());
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Adds the score of a game that was won or abandoned to the bankroll,
+    /// if it was a Vegas game and the bankroll is cumulative
+    pub fn settle(engine: &Engine) {
+        if engine.scoring() != Scoring::Vegas || !Preferences::load().cumulativeBankroll {
+            return;
+        }
+        let mut bankroll = Bankroll::load();
+        bankroll.total += engine.score();
+        if let Err(error) = bankroll.save() {
+            eprintln!("Could not save the bankroll: {}", error);
+        }
+    }
+}
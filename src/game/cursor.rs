@@ -0,0 +1,163 @@
+#![allow(non_snake_case)]
+
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::pile::pile::{FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+
+/// The piles of the top row, left to right
+const TOP_ROW: [PileIdentifier; 6] = [Deck, Discard, Final1, Final2, Final3, Final4];
+
+/// The keyboard cursor: the pile it is on, how many cards of it are covered
+/// and the cards that were picked up and wait for a destination
+pub struct Cursor {
+    /// The pile the cursor is on
+    pub pile: PileIdentifier,
+    /// The number of top cards of the pile the cursor covers (more than 1 only on pile1 to pile7)
+    pub count: usize,
+    /// The pile and the number of top cards that were picked up
+    pub selected: Option<(PileIdentifier, usize)>,
+}
+
+impl Cursor {
+    /// Creates a cursor on pile1
+    pub fn new() -> Cursor {
+        Cursor {
+            pile: Pile1,
+            count: 1,
+            selected: None,
+        }
+    }
+
+    /// Returns the row the pile of the cursor is in
+    fn row(&self) -> Vec<PileIdentifier> {
+        if self.pile.isPile() {
+            PILE_IDENTIFIERS.to_vec()
+        } else {
+            TOP_ROW.to_vec()
+        }
+    }
+
+    /// Returns the number of face-up cards at the top of a pile (the longest run the cursor can cover)
+    pub fn maxCount(engine: &Engine, identifier: PileIdentifier) -> usize {
+        let pile = engine.identifyPile(identifier);
+        if !identifier.isPile() {
+            return pile.size().min(1);
+        }
+        pile.cards.iter().rev().take_while(|card| card.isFaceUp).count()
+    }
+
+    /// Moves the cursor to a pile, covering only the top card
+    pub fn jumpTo(&mut self, identifier: PileIdentifier) {
+        self.pile = identifier;
+        self.count = 1;
+    }
+
+    /// Moves the cursor to one of pile1 to pile7. Choosing the pile the cursor is already on
+    /// covers one more card of the run, wrapping around to the top card.
+    pub fn toPile(&mut self, engine: &Engine, identifier: PileIdentifier) {
+        if self.pile == identifier && self.selected.is_none() {
+            let maxCount = Self::maxCount(engine, identifier);
+            self.count = if self.count < maxCount { self.count + 1 } else { 1 };
+        } else {
+            self.jumpTo(identifier);
+        }
+    }
+
+    /// Moves the cursor to the deck, or to the discard pile if it is on the deck
+    pub fn toggleDeck(&mut self) {
+        self.jumpTo(if self.pile == Deck { Discard } else { Deck });
+    }
+
+    /// Moves the cursor to the next pile of its row in the given direction, wrapping around
+    pub fn moveHorizontally(&mut self, forward: bool) {
+        let row = self.row();
+        let index = row.iter().position(|identifier| *identifier == self.pile).unwrap_or(0);
+        let next = if forward { (index + 1) % row.len() } else { (index + row.len() - 1) % row.len() };
+        self.jumpTo(row[next]);
+    }
+
+    /// Moves the cursor up: covers one more card of a run, or goes to the pile of the top row
+    /// that is closest (when the run cannot grow or cards are picked up)
+    pub fn moveUp(&mut self, engine: &Engine) {
+        if !self.pile.isPile() {
+            return;
+        }
+        if self.selected.is_none() && self.count < Self::maxCount(engine, self.pile) {
+            self.count += 1;
+            return;
+        }
+        self.jumpTo(Self::closest(engine, &self.pile, &TOP_ROW));
+    }
+
+    /// Moves the cursor down: covers one card less of a run, or goes to the pile below
+    pub fn moveDown(&mut self, engine: &Engine) {
+        if self.pile.isPile() {
+            self.count = (self.count - 1).max(1);
+            return;
+        }
+        self.jumpTo(Self::closest(engine, &self.pile, &PILE_IDENTIFIERS));
+    }
+
+    /// Carries out Enter on the cursor: returns the move that drops the picked up cards on the pile
+    /// of the cursor, draws from the deck or flips a face-down card, or else picks up the covered cards
+    pub fn confirm(&mut self, engine: &Engine) -> Option<Move> {
+        if let Some((from, count)) = self.selected.take() {
+            return Move::transfer(from, self.pile, count);
+        }
+        let pile = engine.identifyPile(self.pile);
+        let maxCount = Self::maxCount(engine, self.pile);
+        if self.pile == Deck {
+            Some(if pile.empty() { Move::RecycleDiscard } else { Move::DrawFromDeck })
+        } else if pile.getTopCard().is_some_and(|card| !card.isFaceUp) {
+            Some(Move::Flip(self.pile))
+        } else {
+            if maxCount > 0 {
+                self.selected = Some((self.pile, self.count.min(maxCount)));
+                self.count = 1;
+            }
+            None
+        }
+    }
+
+    /// Carries out F on the cursor: returns the move of a picked up card to its final pile,
+    /// or else moves the cursor to the next final pile
+    pub fn toFoundation(&mut self, engine: &Engine) -> Option<Move> {
+        let Some((from, 1)) = self.selected else {
+            self.nextFinal();
+            return None;
+        };
+        self.selected = None;
+        let card = engine.identifyPile(from).getTopCard()?;
+        engine.foundationFor(card).and_then(|to| Move::transfer(from, to, 1))
+    }
+
+    /// Cycles through the final piles
+    pub fn nextFinal(&mut self) {
+        let next = match FINAL_IDENTIFIERS.iter().position(|identifier| *identifier == self.pile) {
+            Some(index) => FINAL_IDENTIFIERS[(index + 1) % FINAL_IDENTIFIERS.len()],
+            None => Final1,
+        };
+        self.jumpTo(next);
+    }
+
+    /// Returns the pile of a row whose x coordinate is closest to the one of the given pile
+    fn closest(engine: &Engine, from: &PileIdentifier, row: &[PileIdentifier]) -> PileIdentifier {
+        let x = engine.identifyPile(*from).x;
+        row.iter()
+            .min_by(|a, b| {
+                let distanceA = (engine.identifyPile(*(*a)).x - x).abs();
+                let distanceB = (engine.identifyPile(*(*b)).x - x).abs();
+                distanceA.total_cmp(&distanceB)
+            })
+            .cloned()
+            .unwrap_or(Pile1)
+    }
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,8 @@
-#[derive(PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+/// Indicates how many cards are drawn from the deck at once (Easy: 1, Hard: 3)
 pub enum Difficulty {
     Easy,
     Hard,
-}
\ No newline at end of file
+}
@@ -1,57 +1,60 @@
-use std::{env, io, path};
+use std::{env, path, process};
 use ggez::conf::{Conf, WindowMode};
-use ggez::{event, ContextBuilder};
-use crate::game::difficulty::Difficulty;
-use crate::game::difficulty::Difficulty::{Easy, Hard};
-use crate::game::state::State;
+use ggez::{event, ContextBuilder, GameError};
+use crate::game::layout::{MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH};
+use crate::game::manager::SceneManager;
+use crate::game::options::{Options, StatsFormat, USAGE};
+use crate::game::stats::PlayerStats;
 
 pub struct Game {
 }
 
 impl Game {
     pub fn start() -> Game {
-        let difficulty = Self::difficultyHandler();
+        let options = match Options::parse(env::args().skip(1)) {
+            Ok(options) => options,
+            Err(error) => {
+                eprintln!("{}\n{}", error, USAGE);
+                process::exit(2);
+            }
+        };
+        if let Some(format) = options.stats {
+            let stats = PlayerStats::load();
+            match format {
+                StatsFormat::Text => println!("{}", stats.toText()),
+                StatsFormat::Json => println!("{}", stats.toReport()),
+            }
+            process::exit(0);
+        }
 
         let conf = Conf::new().window_mode(WindowMode {
             width: 1300.0,
             height: 700.0,
+            resizable: true,
+            min_width: MIN_WINDOW_WIDTH,
+            min_height: MIN_WINDOW_HEIGHT,
             ..Default::default()
         });
 
-        let (mut ctx, event_loop) = ContextBuilder::new("Solitaire", "Hristo").default_conf(conf.clone()).build().unwrap();
+        let builder = ContextBuilder::new("Solitaire", "Hristo").default_conf(conf.clone());
+        let (mut ctx, event_loop) = builder.build().unwrap_or_else(|error| fail("Could not open the window", error));
 
+        // While developing, the files in resources override the ones compiled into the game
         if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
             let mut path = path::PathBuf::from(manifest_dir);
             path.push("resources");
             ctx.fs.mount(&path, true);
         }
 
-        let state = State::new(&mut ctx, difficulty).unwrap();
-        ctx.gfx.set_window_title("Solitaire");
+        let manager =
+            SceneManager::new(&mut ctx, options.deal, options.position).unwrap_or_else(|error| fail("Could not load the cards", error));
 
-        event::run(ctx, event_loop, state);
+        event::run(ctx, event_loop, manager);
     }
+}
 
-    fn difficultyHandler() -> Difficulty {
-        let mut string = String::new();
-
-        while string.to_lowercase() != "hard" && string.to_lowercase() != "easy" {
-            string.clear();
-            println!("Difficulty: ");
-            io::stdin().read_line(&mut string).unwrap();
-            if string.contains('\n') {
-                string.pop();
-            }
-            if string.contains('\r') {
-                string.pop();
-            }
-
-        }
-        let difficulty: Difficulty =
-            match string.as_str() {
-                "Hard" | "hard" => Hard,
-                _ => Easy
-            };
-        difficulty
-    }
-}
\ No newline at end of file
+/// Reports an error that keeps the game from starting and exits
+fn fail(context: &str, error: GameError) -> ! {
+    eprintln!("{}: {}", context, error);
+    process::exit(1);
+}
@@ -0,0 +1,165 @@
+#![allow(non_snake_case)]
+
+use crate::assets::theme::Theme;
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::pile::pile::{Pile, FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
+use crate::engine::engine::Engine;
+use ggez::graphics::Rect;
+use ggez::Context;
+
+/// The width of the table the layout is designed for: a window this wide and high shows it at scale 1
+pub const DESIGN_WIDTH: f32 = 1300.0;
+/// The height of the table the layout is designed for
+pub const DESIGN_HEIGHT: f32 = 700.0;
+/// The smallest distance between the sides of the table and the outermost piles
+const MARGIN: f32 = 85.0;
+/// The largest horizontal distance between two columns of piles
+const MAX_COLUMN_SPACING: f32 = 220.0;
+/// The y coordinate of the deck, the discard pile and the final piles
+const TOP_Y: f32 = 30.0;
+/// The vertical distance between the bottom of the final piles and the top of pile1 to pile7
+const ROW_GAP: f32 = 160.0;
+/// The horizontal distance between the deck and the discard pile
+const DISCARD_GAP: f32 = 24.0;
+/// The distance between the bottom of the table and the status line
+const MESSAGE_MARGIN: f32 = 40.0;
+/// The smallest distance between the bottom of a pile and the status line
+const PILE_MARGIN: f32 = 10.0;
+/// The distance below a face-up card that a long pile is never compressed beyond
+const MIN_FACE_UP_OFFSET: f32 = 8.0;
+/// The distance below a face-down card that a long pile is never compressed beyond
+const MIN_FACE_DOWN_OFFSET: f32 = 3.0;
+/// The smallest window width
+pub const MIN_WINDOW_WIDTH: f32 = 640.0;
+/// The smallest window height
+pub const MIN_WINDOW_HEIGHT: f32 = 360.0;
+
+/// Where the piles are on the table for a given window size.
+/// The table is drawn in table units, which the window shows `scale` pixels wide: the designed table
+/// always fits, the extra room of a wider window spreads the columns and the one of a higher window
+/// leaves the fans of the piles longer before they are compressed.
+pub struct Layout {
+    /// The number of window pixels per table unit, which is also the scale of the cards
+    pub scale: f32,
+    /// The width of the table in table units (at least the designed width)
+    pub width: f32,
+    /// The height of the table in table units (at least the designed height)
+    pub height: f32,
+    /// The size of a card of the theme in table units
+    pub cardWidth: f32,
+    pub cardHeight: f32,
+    /// The vertical distance between a face-up card of pile1 to pile7 and the card on it, if there is room
+    pub faceUpOffset: f32,
+    /// The y coordinate of pile1 to pile7
+    pileY: f32,
+    /// The x coordinates of the seven columns of piles
+    columns: [f32; 7],
+}
+
+impl Layout {
+    /// Creates the layout of the cards of a theme in a window `windowWidth` by `windowHeight` pixels
+    pub fn new(windowWidth: f32, windowHeight: f32, theme: &Theme) -> Layout {
+        let (windowWidth, windowHeight) = (windowWidth.max(1.0), windowHeight.max(1.0));
+        let scale = (windowWidth / DESIGN_WIDTH).min(windowHeight / DESIGN_HEIGHT);
+        let (width, height) = (windowWidth / scale, windowHeight / scale);
+
+        let cardWidth = theme.cardWidth;
+        let spacing = ((width - 2.0 * MARGIN - cardWidth) / 6.0).min(MAX_COLUMN_SPACING);
+        let first = (width - 6.0 * spacing - cardWidth) / 2.0;
+        let mut columns = [0.0; 7];
+        for (index, column) in columns.iter_mut().enumerate() {
+            *column = first + index as f32 * spacing;
+        }
+
+        Layout {
+            scale,
+            width,
+            height,
+            cardWidth,
+            cardHeight: theme.cardHeight,
+            faceUpOffset: theme.fanOffset,
+            pileY: TOP_Y + theme.cardHeight + ROW_GAP,
+            columns,
+        }
+    }
+
+    /// Creates the layout of the cards of a theme in the current window
+    pub fn fromContext(ctx: &Context, theme: &Theme) -> Layout {
+        let (width, height) = ctx.gfx.drawable_size();
+        Layout::new(width, height, theme)
+    }
+
+    /// Returns the coordinates of a pile
+    pub fn position(&self, identifier: &PileIdentifier) -> (f32, f32) {
+        match identifier {
+            Deck => (self.columns[0], TOP_Y),
+            Discard => (self.columns[0] + self.cardWidth + DISCARD_GAP, TOP_Y),
+            GrabbedCards => (0.0, 0.0),
+            _ => {
+                if let Some(index) = FINAL_IDENTIFIERS.iter().position(|candidate| candidate == identifier) {
+                    (self.columns[index + 2], TOP_Y)
+                } else {
+                    let index = PILE_IDENTIFIERS.iter().position(|pile| pile == identifier).unwrap_or(0);
+                    (self.columns[index], self.pileY)
+                }
+            }
+        }
+    }
+
+    /// Returns the distances below a face-down and below a face-up card of a fanned pile.
+    /// A pile that would reach past the status line is compressed: first its face-down cards,
+    /// then its face-up ones.
+    pub fn fan(&self, pile: &Pile) -> (f32, f32) {
+        let room = self.messageY() - PILE_MARGIN - self.pileY - self.cardHeight;
+        // Only the cards below the top card take room
+        let covered = &pile.cards[..pile.size().saturating_sub(1)];
+        let faceUp = covered.iter().filter(|card| card.isFaceUp).count() as f32;
+        let faceDown = covered.len() as f32 - faceUp;
+
+        // A face-down card shows half as much as a face-up one
+        let (mut faceDownOffset, mut faceUpOffset) = (self.faceUpOffset / 2.0, self.faceUpOffset);
+        if faceDown > 0.0 && faceDown * faceDownOffset + faceUp * faceUpOffset > room {
+            faceDownOffset = ((room - faceUp * faceUpOffset) / faceDown).max(MIN_FACE_DOWN_OFFSET);
+        }
+        if faceUp > 0.0 && faceDown * faceDownOffset + faceUp * faceUpOffset > room {
+            faceUpOffset = ((room - faceDown * faceDownOffset) / faceUp).max(MIN_FACE_UP_OFFSET);
+        }
+        (faceDownOffset, faceUpOffset)
+    }
+
+    /// Moves every pile of an engine to its place, compressing the piles that are too long
+    pub fn place(&self, engine: &mut Engine) {
+        for identifier in [Deck, Discard].into_iter().chain(FINAL_IDENTIFIERS).chain(PILE_IDENTIFIERS) {
+            let (x, y) = self.position(&identifier);
+            let (faceDownOffset, faceUpOffset) = self.fan(engine.identifyPile(identifier));
+            engine.placePile(identifier, x, y, faceDownOffset, faceUpOffset);
+        }
+    }
+
+    /// Returns the area covered by the top `count` cards of a pile (the empty pile if there are none)
+    pub fn area(&self, engine: &Engine, identifier: PileIdentifier, count: usize) -> Rect {
+        let (width, height) = (self.cardWidth, self.cardHeight);
+        let pile = engine.identifyPile(identifier);
+        let count = count.clamp(1, pile.size().max(1));
+        match (pile.getCardByIndex(pile.size().saturating_sub(count)), pile.getTopCard()) {
+            (Some(first), Some(top)) => Rect::new(first.x, first.y, width, top.y - first.y + height),
+            _ => Rect::new(pile.x, pile.y, width, height),
+        }
+    }
+
+    /// Returns the area of the table, to draw it in table units
+    pub fn screen(&self) -> Rect {
+        Rect::new(0.0, 0.0, self.width, self.height)
+    }
+
+    /// Turns window coordinates (e.g. of the mouse) into table coordinates
+    pub fn toTable(&self, x: f32, y: f32) -> (f32, f32) {
+        (x / self.scale, y / self.scale)
+    }
+
+    /// Returns the y coordinate of the status line at the bottom of the table
+    pub fn messageY(&self) -> f32 {
+        self.height - MESSAGE_MARGIN
+    }
+}
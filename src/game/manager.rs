@@ -0,0 +1,167 @@
+#![allow(non_snake_case)]
+
+use crate::assets::assets::Assets;
+use crate::assets::theme::Theme;
+use crate::engine::engine::Engine;
+use crate::game::menu::Menu;
+use crate::game::preferences::{Preferences, DEFAULT_THEME};
+use crate::game::scene::{Scene, Transition};
+use crate::game::settings::Settings;
+use crate::game::state::State;
+use crate::game::statistics::Statistics;
+
+use ggez::conf::FullscreenType;
+use ggez::event::{EventHandler, MouseButton};
+use ggez::input::keyboard::{KeyCode, KeyInput, KeyMods};
+use ggez::{Context, GameResult};
+
+/// Runs the current scene and switches to the next one when the scene asks for it
+pub struct SceneManager {
+    /// All resources, loaded once and shared by the scenes
+    assets: Assets,
+    /// The theme chosen on the settings screen when the assets were loaded. It differs from the theme
+    /// of the assets if that one could not be loaded, which is then not tried again until another is chosen.
+    chosenTheme: String,
+    /// The scene that receives the events
+    current: Box<dyn Scene>,
+    /// Indicates if the window fills the screen
+    fullscreen: bool,
+}
+
+impl SceneManager {
+    /// Creates a SceneManager that starts on the start screen, or plays `position` right away if one is given.
+    /// `deal` is the deal number the first new game should use.
+    pub fn new(ctx: &mut Context, deal: Option<u64>, position: Option<Engine>) -> GameResult<SceneManager> {
+        let chosenTheme = Preferences::load().theme;
+        let assets = SceneManager::loadAssets(ctx, &chosenTheme)?;
+        ctx.gfx.set_window_title("Solitaire");
+
+        let current: Box<dyn Scene> = match position {
+            Some(engine) => Box::new(State::new(ctx, assets.clone(), engine)),
+            None => Box::new(Menu::new(deal, assets.theme.tableColor())),
+        };
+        Ok(SceneManager {
+            current,
+            assets,
+            chosenTheme,
+            fullscreen: false,
+        })
+    }
+
+    /// Loads the cards of the theme `id`, falling back to the default theme if it cannot be loaded
+    /// and to the procedurally drawn cards if neither can
+    fn loadAssets(ctx: &mut Context, id: &str) -> GameResult<Assets> {
+        let mut ids = vec![id];
+        if id != DEFAULT_THEME {
+            ids.push(DEFAULT_THEME);
+        }
+        for id in ids {
+            match Theme::load(ctx, id).and_then(|theme| Assets::new(ctx, theme)) {
+                Ok(assets) => return Ok(assets),
+                Err(error) => eprintln!("Could not load the theme {}: {}", id, error),
+            }
+        }
+        Assets::new(ctx, Theme::fallback())
+    }
+
+    /// Loads the theme chosen on the settings screen if another one was chosen since the last time
+    fn reloadTheme(&mut self, ctx: &mut Context) {
+        let id = Preferences::load().theme;
+        if id == self.chosenTheme {
+            return;
+        }
+        match SceneManager::loadAssets(ctx, &id) {
+            Ok(assets) => self.assets = assets,
+            Err(error) => eprintln!("Could not load the theme {}: {}", id, error),
+        }
+        self.chosenTheme = id;
+    }
+
+    /// Switches between a window and the full screen
+    fn toggleFullscreen(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.fullscreen = !self.fullscreen;
+        let mode = if self.fullscreen { FullscreenType::Desktop } else { FullscreenType::Windowed };
+        ctx.gfx.set_fullscreen(mode)
+    }
+
+    /// Carries out the transition the current scene asked for, if any
+    fn switch(&mut self, ctx: &mut Context) {
+        let transition = self.current.transition();
+        if let Transition::None = transition {
+            return;
+        }
+        // The theme may have been changed on the settings screen
+        self.reloadTheme(ctx);
+        let background = self.assets.theme.tableColor();
+        let mut title = String::from("Solitaire");
+        self.current = match transition {
+            Transition::None => return,
+            Transition::Menu => Box::new(Menu::new(None, background)),
+            Transition::Play(engine) => {
+                if let Some(seed) = engine.seed() {
+                    title = format!("Solitaire - Deal #{}", seed);
+                }
+                Box::new(State::new(ctx, self.assets.clone(), *engine))
+            }
+            Transition::Statistics => Box::new(Statistics::new(background)),
+            Transition::Settings => Box::new(Settings::new(ctx)),
+            Transition::Quit => {
+                ctx.request_quit();
+                return;
+            }
+        };
+        ctx.gfx.set_window_title(&title);
+    }
+}
+
+impl EventHandler for SceneManager {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.current.update(ctx)?;
+        self.switch(ctx);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.current.draw(ctx)
+    }
+
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) -> GameResult<()> {
+        self.current.mouse_button_down_event(ctx, button, x, y)?;
+        self.switch(ctx);
+        Ok(())
+    }
+
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) -> GameResult<()> {
+        self.current.mouse_button_up_event(ctx, button, x, y)?;
+        self.switch(ctx);
+        Ok(())
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> GameResult<()> {
+        self.current.mouse_motion_event(ctx, x, y, dx, dy)?;
+        self.switch(ctx);
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, repeated: bool) -> GameResult<()> {
+        let altEnter = input.keycode == Some(KeyCode::Return) && input.mods.contains(KeyMods::ALT);
+        if input.keycode == Some(KeyCode::F11) || altEnter {
+            return self.toggleFullscreen(ctx);
+        }
+        self.current.key_down_event(ctx, input, repeated)?;
+        self.switch(ctx);
+        Ok(())
+    }
+
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) -> GameResult<()> {
+        self.current.resize_event(ctx, width, height)
+    }
+
+    fn focus_event(&mut self, ctx: &mut Context, gained: bool) -> GameResult<()> {
+        self.current.focus_event(ctx, gained)
+    }
+
+    fn quit_event(&mut self, ctx: &mut Context) -> GameResult<bool> {
+        self.current.quit_event(ctx)
+    }
+}
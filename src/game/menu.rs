@@ -0,0 +1,180 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::save::SaveFile;
+use crate::game::bankroll::Bankroll;
+use crate::game::difficulty::Difficulty;
+use crate::game::paths;
+use crate::game::preferences::Preferences;
+use crate::game::scene::{Scene, Transition};
+use crate::game::stats::PlayerStats;
+use crate::game::ui::{self, ButtonList};
+
+use ggez::event::{EventHandler, MouseButton};
+use ggez::graphics::{Canvas, Color};
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::{Context, GameResult};
+use std::fs;
+
+/// The y coordinate of the title
+const TITLE_Y: f32 = 80.0;
+/// The y coordinate of the first button
+const FIRST_BUTTON_Y: f32 = 220.0;
+/// The y coordinate of the error message
+const MESSAGE_Y: f32 = 620.0;
+
+#[derive(Clone, Copy, PartialEq)]
+/// The choices of the start screen
+enum Item {
+    NewGame(Difficulty),
+    Resume,
+    Statistics,
+    Settings,
+    Quit,
+}
+
+/// The start screen: starts or resumes a game and leads to the other screens
+pub struct Menu {
+    /// The buttons of the choices
+    buttons: ButtonList<Item>,
+    /// The deal number requested on the command line, used by the next new game
+    deal: Option<u64>,
+    /// The error of the last attempt to resume a game
+    message: Option<String>,
+    /// The color of the table of the theme
+    background: Color,
+    /// The transition requested by the last event
+    transition: Transition,
+}
+
+impl Menu {
+    /// Creates the start screen on the table color of the theme.
+    /// `deal` is the deal number the next new game should use.
+    pub fn new(deal: Option<u64>, background: Color) -> Menu {
+        let choices = vec![
+            (Item::NewGame(Difficulty::Easy), "New Game: Draw 1".to_string()),
+            (Item::NewGame(Difficulty::Hard), "New Game: Draw 3".to_string()),
+            (Item::Resume, "Resume".to_string()),
+            (Item::Statistics, "Statistics".to_string()),
+            (Item::Settings, "Settings".to_string()),
+            (Item::Quit, "Quit".to_string()),
+        ];
+        let mut buttons = ButtonList::new(choices, FIRST_BUTTON_Y);
+        if let Some(button) = buttons.button(Item::Resume) {
+            button.enabled = paths::saveFile().is_some_and(|path| path.exists());
+        }
+
+        Menu {
+            buttons,
+            deal,
+            message: None,
+            background,
+            transition: Transition::None,
+        }
+    }
+
+    /// Carries out a choice
+    fn choose(&mut self, item: Item) {
+        self.transition = match item {
+            Item::NewGame(difficulty) => {
+                // Starting a new game abandons the saved one
+                if let Ok(engine) = self.resume() {
+                    PlayerStats::record(&engine, false);
+                    Bankroll::settle(&engine);
+                }
+                let seed = self.deal.unwrap_or_else(rand::random);
+                let engine = Engine::withScoring(difficulty, Preferences::load().scoring, seed);
+                Transition::Play(Box::new(engine))
+            }
+            Item::Resume => match self.resume() {
+                Ok(engine) => Transition::Play(Box::new(engine)),
+                Err(message) => {
+                    self.message = Some(message);
+                    if let Some(button) = self.buttons.button(Item::Resume) {
+                        button.enabled = false;
+                    }
+                    Transition::None
+                }
+            },
+            Item::Statistics => Transition::Statistics,
+            Item::Settings => Transition::Settings,
+            Item::Quit => Transition::Quit,
+        };
+    }
+
+    /// Loads the game saved on the last exit.
+    /// A save file that cannot be loaded is moved aside so that it is not overwritten.
+    fn resume(&self) -> Result<Engine, String> {
+        let path = paths::saveFile().filter(|path| path.exists()).ok_or("There is no saved game")?;
+        SaveFile::read(&path).and_then(SaveFile::intoEngine).map_err(|error| {
+            let backup = path.with_extension("json.bak");
+            match fs::rename(&path, &backup) {
+                Ok(()) => format!("Could not resume: {} (moved to {})", error, backup.display()),
+                Err(_) => format!("Could not resume: {}", error),
+            }
+        })
+    }
+}
+
+impl EventHandler for Menu {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let mut canvas = Canvas::from_frame(ctx, self.background);
+        canvas.set_screen_coordinates(ui::Screen::fromContext(ctx).coordinates());
+
+        ui::drawCentered(ctx, &mut canvas, "Solitaire", TITLE_Y, 72.0)?;
+        if let Some(seed) = self.deal {
+            ui::drawCentered(ctx, &mut canvas, &format!("Deal #{}", seed), TITLE_Y + 80.0, 24.0)?;
+        }
+        self.buttons.draw(ctx, &mut canvas)?;
+        if let Some(message) = &self.message {
+            ui::drawCentered(ctx, &mut canvas, message, MESSAGE_Y, 20.0)?;
+        }
+
+        canvas.finish(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        _button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult<()> {
+        let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+        if let Some(item) = self.buttons.at(x, y) {
+            self.choose(item);
+        }
+        Ok(())
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, _dx: f32, _dy: f32) -> GameResult<()> {
+        let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+        self.buttons.hover(x, y);
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, _repeated: bool) -> GameResult<()> {
+        match input.keycode {
+            Some(KeyCode::Up) => self.buttons.moveSelection(false),
+            Some(KeyCode::Down) | Some(KeyCode::Tab) => self.buttons.moveSelection(true),
+            Some(KeyCode::Return) | Some(KeyCode::NumpadEnter) | Some(KeyCode::Space) => {
+                if let Some(item) = self.buttons.selected() {
+                    self.choose(item);
+                }
+            }
+            Some(KeyCode::Escape) => self.transition = Transition::Quit,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Scene for Menu {
+    fn transition(&mut self) -> Transition {
+        std::mem::take(&mut self.transition)
+    }
+}
@@ -1,3 +1,30 @@
-pub mod state;
+#[cfg(feature = "gui")]
+pub mod animation;
+pub mod arguments;
+pub mod bankroll;
+pub mod cursor;
 pub mod difficulty;
-pub mod game;
\ No newline at end of file
+#[cfg(feature = "gui")]
+pub mod game;
+#[cfg(feature = "gui")]
+pub mod layout;
+#[cfg(feature = "gui")]
+pub mod manager;
+#[cfg(feature = "gui")]
+pub mod menu;
+pub mod options;
+pub mod paths;
+pub mod preferences;
+#[cfg(feature = "gui")]
+pub mod scene;
+#[cfg(feature = "gui")]
+pub mod settings;
+#[cfg(feature = "gui")]
+pub mod state;
+#[cfg(feature = "gui")]
+pub mod statistics;
+pub mod stats;
+#[cfg(feature = "gui")]
+pub mod ui;
+#[cfg(feature = "gui")]
+pub mod victory;
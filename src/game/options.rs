@@ -0,0 +1,85 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::notation::NotationError;
+use crate::game::arguments::Arguments;
+use std::fmt;
+
+/// The usage text printed when the command line cannot be parsed
+pub const USAGE: &str = "Usage: solitaire [--deal <number>] [--position <text>] [--stats [text|json]]";
+
+#[derive(Clone, Debug, Default)]
+/// The options given on the command line
+pub struct Options {
+    /// The deal number (seed) to start with instead of a random one
+    pub deal: Option<u64>,
+    /// The position to play from instead of showing the start screen
+    pub position: Option<Engine>,
+    /// Print the statistics in this format instead of starting the game
+    pub stats: Option<StatsFormat>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The format `--stats` prints the statistics in
+pub enum StatsFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+/// The reason the command line could not be parsed
+pub enum OptionsError {
+    /// An option that is not supported
+    UnknownOption(String),
+    /// An option that requires a value was given without one
+    MissingValue(String),
+    /// The value of an option could not be parsed
+    InvalidValue(String, String),
+    /// The position given with `--position` could not be read
+    InvalidPosition(NotationError),
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionsError::UnknownOption(option) => write!(f, "unknown option '{}'", option),
+            OptionsError::MissingValue(option) => write!(f, "'{}' requires a value", option),
+            OptionsError::InvalidValue(option, value) => {
+                write!(f, "invalid value '{}' for '{}'", value, option)
+            }
+            OptionsError::InvalidPosition(error) => write!(f, "invalid position for '--position': {}", error),
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+impl Options {
+    /// Parses the given arguments (without the program name)
+    pub fn parse<I: IntoIterator<Item = String>>(arguments: I) -> Result<Options, OptionsError> {
+        let mut options = Options::default();
+        let mut arguments = Arguments::new(arguments);
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--deal" => options.deal = Some(arguments.deal(&argument)?),
+                "--position" => options.position = Some(arguments.position(&argument)?),
+                "--stats" => {
+                    let format = match arguments.peek() {
+                        Some("json") => StatsFormat::Json,
+                        Some("text") => StatsFormat::Text,
+                        _ => {
+                            options.stats = Some(StatsFormat::Text);
+                            continue;
+                        }
+                    };
+                    arguments.next();
+                    options.stats = Some(format);
+                }
+                _ => return Err(OptionsError::UnknownOption(argument)),
+            }
+        }
+
+        Ok(options)
+    }
+}
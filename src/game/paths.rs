@@ -0,0 +1,29 @@
+#![allow(non_snake_case)]
+
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+/// Returns the per-user data directory of the game (the same one ggez uses), if there is a home directory
+pub fn dataDir() -> Option<PathBuf> {
+    ProjectDirs::from("", "Hristo", "Solitaire").map(|dirs| dirs.data_local_dir().to_path_buf())
+}
+
+/// Returns the path of the game in progress
+pub fn saveFile() -> Option<PathBuf> {
+    dataDir().map(|dir| dir.join("save.json"))
+}
+
+/// Returns the path of the choices made on the settings screen
+pub fn preferencesFile() -> Option<PathBuf> {
+    dataDir().map(|dir| dir.join("preferences.json"))
+}
+
+/// Returns the path of the cumulative Vegas bankroll
+pub fn bankrollFile() -> Option<PathBuf> {
+    dataDir().map(|dir| dir.join("bankroll.json"))
+}
+
+/// Returns the path of the player statistics
+pub fn statsFile() -> Option<PathBuf> {
+    dataDir().map(|dir| dir.join("stats.json"))
+}
@@ -0,0 +1,106 @@
+#![allow(non_snake_case)]
+
+use crate::engine::scoring::Scoring;
+use crate::game::paths;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::{fs, io};
+
+/// The theme used when none was chosen or the chosen one cannot be loaded
+pub const DEFAULT_THEME: &str = "classic";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+/// How fast the cards move across the table
+pub enum AnimationSpeed {
+    Off,
+    Fast,
+    #[default]
+    Normal,
+    Slow,
+}
+
+impl AnimationSpeed {
+    /// Returns the time in seconds a card takes to reach its pile (0 when the cards do not move)
+    pub fn seconds(self) -> f32 {
+        match self {
+            AnimationSpeed::Off => 0.0,
+            AnimationSpeed::Fast => 0.12,
+            AnimationSpeed::Normal => 0.25,
+            AnimationSpeed::Slow => 0.45,
+        }
+    }
+
+    /// Returns the speed that follows on the settings screen
+    pub fn next(self) -> AnimationSpeed {
+        match self {
+            AnimationSpeed::Off => AnimationSpeed::Fast,
+            AnimationSpeed::Fast => AnimationSpeed::Normal,
+            AnimationSpeed::Normal => AnimationSpeed::Slow,
+            AnimationSpeed::Slow => AnimationSpeed::Off,
+        }
+    }
+}
+
+impl Display for AnimationSpeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AnimationSpeed::Off => "Off",
+            AnimationSpeed::Fast => "Fast",
+            AnimationSpeed::Normal => "Normal",
+            AnimationSpeed::Slow => "Slow",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+/// The choices made on the settings screen
+pub struct Preferences {
+    /// The rule set new games keep the score with
+    pub scoring: Scoring,
+    /// Indicates if the Vegas scores of all games are added up into one bankroll
+    pub cumulativeBankroll: bool,
+    /// Indicates if undoing (and redoing) a move counts as a move on the move counter
+    pub undoCountsAsMove: bool,
+    /// Indicates if a game is auto-completed as soon as every card is face up (otherwise on request)
+    pub autoComplete: bool,
+    /// How fast the cards are dealt and moved
+    pub animationSpeed: AnimationSpeed,
+    /// The directory name of the theme of the cards and the table
+    pub theme: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            scoring: Scoring::default(),
+            cumulativeBankroll: false,
+            undoCountsAsMove: true,
+            autoComplete: true,
+            animationSpeed: AnimationSpeed::default(),
+            theme: DEFAULT_THEME.to_string(),
+        }
+    }
+}
+
+impl Preferences {
+    /// Reads the preferences, falling back to the defaults if there are none or they cannot be read
+    pub fn load() -> Preferences {
+        paths::preferencesFile()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the preferences, creating the directory if needed
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = paths::preferencesFile() else {
+            return Ok(());
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
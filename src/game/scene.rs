@@ -0,0 +1,28 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use ggez::event::EventHandler;
+
+#[derive(Default)]
+/// What the scene manager should do after an event was handled by a scene
+pub enum Transition {
+    /// Keep the current scene
+    #[default]
+    None,
+    /// Show the start screen
+    Menu,
+    /// Play the game of the given engine
+    Play(Box<Engine>),
+    /// Show the statistics screen
+    Statistics,
+    /// Show the settings screen
+    Settings,
+    /// Close the window
+    Quit,
+}
+
+/// A screen of the game (the start screen, the table, ...) that handles its own events
+pub trait Scene: EventHandler {
+    /// Returns the transition requested while handling the last event and resets it
+    fn transition(&mut self) -> Transition;
+}
@@ -0,0 +1,213 @@
+#![allow(non_snake_case)]
+
+use crate::assets::theme::Theme;
+use crate::engine::scoring::Scoring;
+use crate::game::bankroll::Bankroll;
+use crate::game::preferences::Preferences;
+use crate::game::scene::{Scene, Transition};
+use crate::game::ui::{self, ButtonList};
+
+use ggez::event::{EventHandler, MouseButton};
+use ggez::graphics::Canvas;
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::{Context, GameResult};
+
+/// The y coordinate of the title
+const TITLE_Y: f32 = 80.0;
+/// The y coordinate of the first button
+const FIRST_BUTTON_Y: f32 = 130.0;
+/// The y coordinate of the explanation below the buttons
+const NOTE_Y: f32 = 590.0;
+
+#[derive(Clone, Copy, PartialEq)]
+/// The choices of the settings screen
+enum Item {
+    Scoring,
+    Bankroll,
+    UndoCountsAsMove,
+    AutoComplete,
+    Animations,
+    Theme,
+    Back,
+}
+
+/// The settings screen
+pub struct Settings {
+    /// The buttons of the choices
+    buttons: ButtonList<Item>,
+    /// The choices that are shown and written on every change
+    preferences: Preferences,
+    /// The cumulative Vegas bankroll
+    bankroll: i32,
+    /// The themes that can be chosen
+    themes: Vec<Theme>,
+    /// The transition requested by the last event
+    transition: Transition,
+}
+
+impl Settings {
+    /// Creates the settings screen
+    pub fn new(ctx: &Context) -> Settings {
+        let preferences = Preferences::load();
+        let choices = vec![
+            (Item::Scoring, String::new()),
+            (Item::Bankroll, String::new()),
+            (Item::UndoCountsAsMove, String::new()),
+            (Item::AutoComplete, String::new()),
+            (Item::Animations, String::new()),
+            (Item::Theme, String::new()),
+            (Item::Back, "Back".to_string()),
+        ];
+        let mut settings = Settings {
+            buttons: ButtonList::new(choices, FIRST_BUTTON_Y),
+            preferences,
+            bankroll: Bankroll::load().total,
+            themes: Theme::available(ctx),
+            transition: Transition::None,
+        };
+        settings.updateLabels();
+        settings
+    }
+
+    /// Writes the current preferences on the buttons
+    fn updateLabels(&mut self) {
+        let scoring = format!("Scoring: {}", self.preferences.scoring);
+        let bankroll = if self.preferences.cumulativeBankroll {
+            "Vegas bankroll: Cumulative"
+        } else {
+            "Vegas bankroll: Per game"
+        };
+        if let Some(button) = self.buttons.button(Item::Scoring) {
+            button.label = scoring;
+        }
+        let undo = if self.preferences.undoCountsAsMove {
+            "Undo counts as a move: Yes"
+        } else {
+            "Undo counts as a move: No"
+        };
+        let autoComplete = if self.preferences.autoComplete {
+            "Auto-complete: Automatic"
+        } else {
+            "Auto-complete: On request"
+        };
+        if let Some(button) = self.buttons.button(Item::Bankroll) {
+            button.label = bankroll.to_string();
+        }
+        if let Some(button) = self.buttons.button(Item::UndoCountsAsMove) {
+            button.label = undo.to_string();
+        }
+        if let Some(button) = self.buttons.button(Item::AutoComplete) {
+            button.label = autoComplete.to_string();
+        }
+        if let Some(button) = self.buttons.button(Item::Animations) {
+            button.label = format!("Animations: {}", self.preferences.animationSpeed);
+        }
+        let theme = self.theme().name;
+        if let Some(button) = self.buttons.button(Item::Theme) {
+            button.label = format!("Theme: {}", theme);
+        }
+    }
+
+    /// Returns the chosen theme (the default one if it cannot be found)
+    fn theme(&self) -> Theme {
+        self.themes
+            .iter()
+            .find(|theme| theme.id == self.preferences.theme)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Chooses the theme that follows the current one
+    fn nextTheme(&mut self) {
+        let index = self.themes.iter().position(|theme| theme.id == self.preferences.theme);
+        let next = index.map_or(0, |index| index + 1) % self.themes.len().max(1);
+        if let Some(theme) = self.themes.get(next) {
+            self.preferences.theme = theme.id.clone();
+        }
+    }
+
+    /// Carries out a choice
+    fn choose(&mut self, item: Item) {
+        match item {
+            Item::Scoring => {
+                self.preferences.scoring = match self.preferences.scoring {
+                    Scoring::Standard => Scoring::Vegas,
+                    Scoring::Vegas => Scoring::Standard,
+                };
+            }
+            Item::Bankroll => self.preferences.cumulativeBankroll = !self.preferences.cumulativeBankroll,
+            Item::UndoCountsAsMove => self.preferences.undoCountsAsMove = !self.preferences.undoCountsAsMove,
+            Item::AutoComplete => self.preferences.autoComplete = !self.preferences.autoComplete,
+            Item::Animations => self.preferences.animationSpeed = self.preferences.animationSpeed.next(),
+            Item::Theme => self.nextTheme(),
+            Item::Back => {
+                self.transition = Transition::Menu;
+                return;
+            }
+        }
+        self.updateLabels();
+        if let Err(error) = self.preferences.save() {
+            eprintln!("Could not save the settings: {}", error);
+        }
+    }
+}
+
+impl EventHandler for Settings {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let mut canvas = Canvas::from_frame(ctx, self.theme().tableColor());
+        canvas.set_screen_coordinates(ui::Screen::fromContext(ctx).coordinates());
+        ui::drawCentered(ctx, &mut canvas, "Settings", TITLE_Y, 56.0)?;
+        self.buttons.draw(ctx, &mut canvas)?;
+        ui::drawCentered(ctx, &mut canvas, "The scoring applies to new games.", NOTE_Y, 20.0)?;
+        if self.preferences.cumulativeBankroll {
+            let bankroll = format!("Bankroll: ${}", self.bankroll);
+            ui::drawCentered(ctx, &mut canvas, &bankroll, NOTE_Y + 32.0, 20.0)?;
+        }
+        canvas.finish(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        _button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult<()> {
+        let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+        if let Some(item) = self.buttons.at(x, y) {
+            self.choose(item);
+        }
+        Ok(())
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, _dx: f32, _dy: f32) -> GameResult<()> {
+        let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+        self.buttons.hover(x, y);
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, _repeated: bool) -> GameResult<()> {
+        match input.keycode {
+            Some(KeyCode::Up) => self.buttons.moveSelection(false),
+            Some(KeyCode::Down) | Some(KeyCode::Tab) => self.buttons.moveSelection(true),
+            Some(KeyCode::Return) | Some(KeyCode::NumpadEnter) | Some(KeyCode::Space) => {
+                if let Some(item) = self.buttons.selected() {
+                    self.choose(item);
+                }
+            }
+            Some(KeyCode::Escape) => self.transition = Transition::Menu,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Scene for Settings {
+    fn transition(&mut self) -> Transition {
+        std::mem::take(&mut self.transition)
+    }
+}
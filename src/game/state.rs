@@ -1,379 +1,692 @@
 #![allow(non_snake_case)]
 
 use crate::assets::assets::Assets;
-use crate::card::card::{Card, CARD_HEIGHT, CARD_WIDTH};
-use crate::card::pile::coordinates::*;
+use crate::card::card::Card;
 use crate::card::pile::identifier::PileIdentifier;
 use crate::card::pile::identifier::PileIdentifier::*;
-use crate::card::pile::pile::{Pile, CARD_OFFSET, FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
-use crate::card::rank::Rank;
-use crate::card::suit::Suit;
-use crate::game::difficulty::Difficulty;
-use crate::game::difficulty::Difficulty::Easy;
+use crate::card::pile::pile::{Pile, FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use crate::engine::notation;
+use crate::engine::save::SaveFile;
+use crate::engine::scoring::Scoring;
+use crate::game::animation::{Animator, Easing};
+use crate::game::bankroll::Bankroll;
+use crate::game::cursor::Cursor;
+use crate::game::layout::Layout;
+use crate::game::paths;
+use crate::game::preferences::Preferences;
+use crate::game::stats::{self, PlayerStats};
+use crate::game::scene::{Scene, Transition};
+use crate::game::ui;
+use crate::game::victory::Victory;
+use crate::solver::hint;
 
 use ggez::event::{EventHandler, MouseButton};
-use ggez::graphics::{Color, Sampler};
+use ggez::input::keyboard::{KeyCode, KeyInput, KeyMods};
+use ggez::graphics::{Canvas, Color, DrawMode, Mesh, Rect, Sampler, Text};
 use ggez::{graphics, Context, GameResult};
-use rand::prelude::SliceRandom;
-use rand::thread_rng;
-
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// The tint of the cards and piles a hint points at
+const HINT_TINT: Color = Color::new(1.0, 1.0, 0.45, 1.0);
+/// The hint message when only the deck can be used
+const DRAW_MESSAGE: &str = "No moves — draw from deck";
+/// The hint message when nothing can be done
+const STUCK_MESSAGE: &str = "No moves left";
+/// The distance between the HUD and the right edge of the window
+const HUD_MARGIN: f32 = 30.0;
+/// The size of the text of the position shown with P
+const NOTATION_SCALE: f32 = 18.0;
+/// The color of the panel behind the position (the table shows through)
+const NOTATION_BACKGROUND: Color = Color::new(0.0, 0.0, 0.0, 0.75);
+/// The message shown when the game can be auto-completed on request
+const AUTO_COMPLETE_MESSAGE: &str = "Press A to auto-complete";
+/// The time in seconds between two moves of an auto-complete
+const AUTO_COMPLETE_SECONDS: f32 = 0.1;
+/// The color of the keyboard cursor
+const CURSOR_COLOR: Color = Color::new(0.3, 0.8, 1.0, 1.0);
+/// The color of the ring around the cards picked up with the keyboard
+const SELECTED_COLOR: Color = HINT_TINT;
+/// The distance between a focus ring and the cards it surrounds
+const RING_MARGIN: f32 = 4.0;
+/// The longest time between the clicks of a double-click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+/// The farthest the mouse may move between the clicks of a double-click
+const DOUBLE_CLICK_DISTANCE: f32 = 6.0;
+
+/// The ggez front end: draws the engine's piles and turns mouse input into moves
 pub struct State {
-    /// Indicates if the game is over
-    gameOver: bool,
     /// All resources
     assets: Assets,
-    /// The first pile (left -> right)
-    pile1: Pile,
-    /// The second pile (left -> right)
-    pile2: Pile,
-    /// The third pile (left -> right)
-    pile3: Pile,
-    /// The fourth pile (left -> right)
-    pile4: Pile,
-    /// The fifth pile (left -> right)
-    pile5: Pile,
-    /// The sixth pile (left -> right)
-    pile6: Pile,
-    /// The seventh pile (left -> right)
-    pile7: Pile,
-    /// The deck
-    deck: Pile,
-    /// The discard pile
-    discard: Pile,
-    /// The first final pile (left -> right)
-    final1: Pile,
-    /// The second final pile (left -> right)
-    final2: Pile,
-    /// The third final pile (left -> right)
-    final3: Pile,
-    /// The fourth final pile (left -> right)
-    final4: Pile,
+    /// The game rules and the piles
+    engine: Engine,
+    /// Where the piles are for the current window size
+    layout: Layout,
     /// Indicates if the mouse button is pressed
     mouseDown: bool,
-    /// Stores the "grabbed" cards
+    /// The time and position of the last left click, used to detect double-clicks
+    lastClick: Option<(Instant, f32, f32)>,
+    /// The keyboard cursor (None until the keyboard is used to play, and again after a mouse click)
+    cursor: Option<Cursor>,
+    /// Stores copies of the "grabbed" cards while they are dragged (the originals stay in the engine)
     grabbedCards: Pile,
-    /// Indicates the game difficulty
-    difficulty: Difficulty,
+    /// The useful moves of the current position, best first (empty until a hint is requested)
+    hints: Vec<Move>,
+    /// The index of the hint that is shown
+    hintIndex: Option<usize>,
+    /// The message shown when a hint was requested but there are no useful moves
+    hintMessage: Option<&'static str>,
+    /// The position in the text notation, shown after P was pressed until the position changes
+    notation: Option<String>,
+    /// Indicates if the window has the focus (the clock is paused while it does not)
+    focused: bool,
+    /// The text of the HUD, updated every frame
+    hud: String,
+    /// Indicates if the game is auto-completed as soon as it is mechanically won (otherwise on request)
+    autoCompleteAutomatically: bool,
+    /// Indicates if an auto-complete is running
+    autoCompleting: bool,
+    /// Indicates if the player stopped an auto-complete, so it is not started again automatically
+    autoCompleteStopped: bool,
+    /// The time in seconds until the next move of the auto-complete
+    autoCompleteTimer: f32,
+    /// Moves the cards across the table
+    animator: Animator,
+    /// Indicates if the win was already added to the statistics and the bankroll
+    finished: bool,
+    /// The cumulative Vegas bankroll before this game (None if Vegas scores are not added up)
+    bankroll: Option<i32>,
+    /// The overlay shown once the game is won
+    victory: Option<Victory>,
+    /// The transition requested by the last event
+    transition: Transition,
 }
 
 impl State {
-    /// Creates a new State
-    pub(crate) fn new(ctx: &mut Context, difficulty: Difficulty) -> GameResult<State> {
-        let assets = Assets::new(ctx)?;
-        let deck = Pile::new(DECK_X, DECK_Y, Deck);
-        let discard = Pile::new(DISCARD_X, DISCARD_Y, Discard);
-
-        let final1 = Pile::new(FINAL1_X, FINAL_Y, Final1);
-        let final2 = Pile::new(FINAL2_X, FINAL_Y, Final2);
-        let final3 = Pile::new(FINAL3_X, FINAL_Y, Final3);
-        let final4 = Pile::new(FINAL4_X, FINAL_Y, Final4);
-
-        let pile1 = Pile::new(PILE1_X, PILE_Y, Pile1);
-        let pile2 = Pile::new(PILE2_X, PILE_Y, Pile2);
-        let pile3 = Pile::new(PILE3_X, PILE_Y, Pile3);
-        let pile4 = Pile::new(PILE4_X, PILE_Y, Pile4);
-        let pile5 = Pile::new(PILE5_X, PILE_Y, Pile5);
-        let pile6 = Pile::new(PILE6_X, PILE_Y, Pile6);
-        let pile7 = Pile::new(PILE7_X, PILE_Y, Pile7);
-
-        let mut state = State {
-            gameOver: false,
+    /// Creates a new State that plays the game of the given engine
+    pub(crate) fn new(ctx: &Context, mut assets: Assets, mut engine: Engine) -> State {
+        let preferences = Preferences::load();
+        engine.setUndoCountsAsMove(preferences.undoCountsAsMove);
+        let cumulative = engine.scoring() == Scoring::Vegas && preferences.cumulativeBankroll;
+        let bankroll = cumulative.then(|| Bankroll::load().total);
+        let layout = Layout::fromContext(ctx, &assets.theme);
+        assets.setScale(layout.scale);
+        layout.place(&mut engine);
+        let mut grabbedCards = Pile::new(0.0, 0.0, GrabbedCards);
+        grabbedCards.offset = layout.faceUpOffset;
+        let mut animator = Animator::new(preferences.animationSpeed.seconds(), Easing::EaseOut);
+        if engine.moves() == 0 && engine.elapsed().is_zero() {
+            let deck = engine.identifyPile(Deck);
+            animator.deal(deck.x, deck.y, &Self::dealtCards(&engine));
+        }
+
+        State {
             assets,
-            pile1,
-            pile2,
-            pile3,
-            pile4,
-            pile5,
-            pile6,
-            pile7,
-            deck,
-            discard,
-            final1,
-            final2,
-            final3,
-            final4,
+            finished: engine.isGameOver(),
+            engine,
+            layout,
             mouseDown: false,
-            grabbedCards: Pile::new(0.0, 0.0, GrabbedCards),
-            difficulty,
+            lastClick: None,
+            cursor: None,
+            grabbedCards,
+            hints: Vec::new(),
+            hintIndex: None,
+            hintMessage: None,
+            notation: None,
+            autoCompleteAutomatically: preferences.autoComplete,
+            autoCompleting: false,
+            autoCompleteStopped: false,
+            autoCompleteTimer: 0.0,
+            animator,
+            focused: true,
+            hud: String::new(),
+            bankroll,
+            victory: None,
+            transition: Transition::None,
+        }
+    }
+
+    /// Writes the game in progress to the save file, or removes the save file if the game is over
+    fn save(&self) {
+        let Some(path) = paths::saveFile() else {
+            return;
         };
+        if self.engine.isGameOver() {
+            let _ = fs::remove_file(path);
+        } else if let Err(error) = SaveFile::fromEngine(&self.engine).write(&path) {
+            eprintln!("Could not save the game to {}: {}", path.display(), error);
+        }
+    }
 
-        state.newDeck();
-        state.dealCards();
-
-        Ok(state)
-    }
-
-    /// Generates a new deck with 52 cards and then shuffles it
-    fn newDeck(&mut self) {
-        let mut deck: Vec<Card> = Vec::new();
-        for suit in vec![Suit::Heart, Suit::Diamond, Suit::Club, Suit::Spade] {
-            for rank in vec![
-                Rank::Ace,
-                Rank::Two,
-                Rank::Three,
-                Rank::Four,
-                Rank::Five,
-                Rank::Six,
-                Rank::Seven,
-                Rank::Eight,
-                Rank::Nine,
-                Rank::Ten,
-                Rank::Jack,
-                Rank::Queen,
-                Rank::King,
-            ] {
-                let card: Card = Card::new(suit.clone(), rank.clone(), self.assets.clone());
-                deck.push(card);
+    /// Returns the cards of the tableau in the order they are dealt: row by row, from left to right
+    fn dealtCards(engine: &Engine) -> Vec<Card> {
+        let mut cards = Vec::new();
+        for row in 0..PILE_IDENTIFIERS.len() {
+            for identifier in PILE_IDENTIFIERS.into_iter().skip(row) {
+                if let Some(card) = engine.identifyPile(identifier).getCardByIndex(row) {
+                    cards.push(*card);
+                }
             }
         }
+        cards
+    }
 
-        let mut rng = thread_rng();
-        deck.shuffle(&mut rng);
+    /// Returns every card of the engine where its pile puts it
+    fn tableCards(&self) -> Vec<Card> {
+        [Deck, Discard]
+            .into_iter()
+            .chain(FINAL_IDENTIFIERS)
+            .chain(PILE_IDENTIFIERS)
+            .flat_map(|identifier| self.engine.identifyPile(identifier).cards.clone())
+            .collect()
+    }
 
-        for card in deck {
-            self.deck.addNoOffset(card);
+    /// Returns every card of the engine where it is seen: dragged, on its way or on its pile
+    fn visibleCards(&self) -> Vec<Card> {
+        let mut cards = self.tableCards();
+        for card in cards.iter_mut() {
+            let grabbed = self
+                .grabbedCards
+                .cards
+                .iter()
+                .position(|grabbed| grabbed.suit == card.suit && grabbed.rank == card.rank);
+            if let Some(index) = grabbed {
+                card.x = self.grabbedCards.cards[index].x;
+                card.y = self.grabbedCards.cards[index].y + index as f32 * self.grabbedCards.offset;
+            } else if let Some(current) = self.animator.current(card) {
+                *card = current;
+            }
         }
+        cards
     }
 
-    /// "Deals" the cards from a generated deck to the piles and the rest remain in the deck
-    fn dealCards(&mut self) {
-        for i in 0..7 {
-            for j in 0..i + 1 {
-                let mut card = self.deck.removeCard();
-                if j == i {
-                    card.as_mut().unwrap().isFaceUp = true;
-                }
-                match i {
-                    0 => self.pile1.addCard(card.unwrap()),
-                    1 => self.pile2.addCard(card.unwrap()),
-                    2 => self.pile3.addCard(card.unwrap()),
-                    3 => self.pile4.addCard(card.unwrap()),
-                    4 => self.pile5.addCard(card.unwrap()),
-                    5 => self.pile6.addCard(card.unwrap()),
-                    6 => self.pile7.addCard(card.unwrap()),
-                    _ => panic!("Invalid pile"),
-                }
+    /// Applies a move to the engine and sends the cards it moved on their way.
+    /// Illegal moves are ignored; returns true if the move was applied.
+    fn apply(&mut self, action: Move) -> bool {
+        let before = self.visibleCards();
+        let applied = self.engine.apply(action).is_ok();
+        if applied {
+            self.layout.place(&mut self.engine);
+            self.animator.moveCards(&before, &self.tableCards());
+            self.clearHint();
+            self.autoCompleteStopped = false;
+            if self.engine.isGameOver() && !self.finished {
+                self.finished = true;
+                PlayerStats::record(&self.engine, true);
+                Bankroll::settle(&self.engine);
             }
         }
+        applied
     }
 
-    /// Checks for win conditions
-    fn checkForWin(&mut self) {
-        self.gameOver = self.final1.size() == 13
-            && self.final2.size() == 13
-            && self.final3.size() == 13
-            && self.final4.size() == 13;
-    }
-
-    /// Identifies a pile by a given PileIdentifier and returns a reference to the pile
-    fn identifyPile(&self, identifier: PileIdentifier) -> &Pile {
-        match identifier {
-            Pile1 => &self.pile1,
-            Pile2 => &self.pile2,
-            Pile3 => &self.pile3,
-            Pile4 => &self.pile4,
-            Pile5 => &self.pile5,
-            Pile6 => &self.pile6,
-            Pile7 => &self.pile7,
-            Final1 => &self.final1,
-            Final2 => &self.final2,
-            Final3 => &self.final3,
-            Final4 => &self.final4,
-            Deck => &self.deck,
-            Discard => &self.discard,
-            GrabbedCards => &self.grabbedCards,
-        }
-    }
-
-    /// Identifies a pile by a given PileIdentifier and returns a mutable reference to the pile
-    fn identifyPileMut(&mut self, identifier: PileIdentifier) -> &mut Pile {
-        match identifier {
-            Pile1 => &mut self.pile1,
-            Pile2 => &mut self.pile2,
-            Pile3 => &mut self.pile3,
-            Pile4 => &mut self.pile4,
-            Pile5 => &mut self.pile5,
-            Pile6 => &mut self.pile6,
-            Pile7 => &mut self.pile7,
-            Final1 => &mut self.final1,
-            Final2 => &mut self.final2,
-            Final3 => &mut self.final3,
-            Final4 => &mut self.final4,
-            Deck => &mut self.deck,
-            Discard => &mut self.discard,
-            GrabbedCards => &mut self.grabbedCards,
+    /// Undoes (`redo` false) or redoes the last move, sending the cards back on their way
+    fn rewind(&mut self, redo: bool) {
+        self.stopAutoComplete();
+        self.animator.stopCascade();
+        let before = self.visibleCards();
+        if redo {
+            self.engine.redo();
+        } else {
+            self.engine.undo();
         }
+        self.layout.place(&mut self.engine);
+        self.animator.moveCards(&before, &self.tableCards());
+        self.clearHint();
     }
 
-    /// Checks if the mouse is over a card with coordinates x and y
-    fn mouseOver(&self, ctx: &mut Context, x: f32, y: f32) -> bool {
-        let mouseX = ctx.mouse.position().x;
-        let mouseY = ctx.mouse.position().y;
-        mouseX > x && mouseX < x + CARD_WIDTH && mouseY > y && mouseY < y + CARD_HEIGHT
+    /// Checks if a left click at (x, y) completes a double-click, and remembers it for the next one
+    fn isDoubleClick(&mut self, x: f32, y: f32) -> bool {
+        let now = Instant::now();
+        let double = self.lastClick.is_some_and(|(time, lastX, lastY)| {
+            now - time <= DOUBLE_CLICK_TIME
+                && (x - lastX).abs() <= DOUBLE_CLICK_DISTANCE
+                && (y - lastY).abs() <= DOUBLE_CLICK_DISTANCE
+        });
+        self.lastClick = if double { None } else { Some((now, x, y)) };
+        double
     }
 
-    /// Checks if the mouse is over a pile but not over the top card
-    fn mouseIsOverPile(&self, ctx: &mut Context, pile: Pile) -> bool {
-        let mouseX = ctx.mouse.position().x;
-        let mouseY = ctx.mouse.position().y;
-        mouseX > pile.x
-            && mouseX < pile.x + CARD_WIDTH
-            && mouseY > pile.y
-            && mouseY < pile.y + (pile.size() as i32 - 1) as f32 * CARD_OFFSET
-    }
-
-    /// A helper function for canBeStacked
-    fn stackableCheckForFinal(&self, card: Card, pile: Pile) -> bool {
-        let topCard = pile.getTopCard();
-        (topCard.is_none() && card.rank == Rank::Ace)
-            || (topCard.is_some()
-                && card.suit == topCard.unwrap().suit
-                && card.rank as i32 == topCard.unwrap().clone().rank as i32 + 1)
-    }
-
-    /// A helper function for canBeStacked
-    fn stackableCheckForPile(&self, card: Card, pile: Pile) -> bool {
-        let topCard = pile.getTopCard();
-        (topCard.is_none() && card.rank == Rank::King)
-            || (topCard.is_some()
-                && card.suit.getColor() != topCard.unwrap().suit.getColor()
-                && card.rank as i32 == topCard.unwrap().clone().rank as i32 - 1)
-    }
-
-    /// A function that checks if a given card can be placed on a given pile
-    fn canBeStacked(&self, card: Card, receiverIdentifier: PileIdentifier) -> bool {
-        match receiverIdentifier {
-            Final1 | Final2 | Final3 | Final4 => {
-                self.stackableCheckForFinal(card, self.identifyPile(receiverIdentifier).clone())
-            }
-            Pile1 | Pile2 | Pile3 | Pile4 | Pile5 | Pile6 | Pile7 => {
-                self.stackableCheckForPile(card, self.identifyPile(receiverIdentifier).clone())
+    /// Moves the face-up top card of a pile or of the discard pile under the mouse to its final pile
+    fn sendToFoundation(&mut self, ctx: &mut Context) {
+        let source = PILE_IDENTIFIERS.into_iter().chain([Discard]).find(|identifier| {
+            self.engine
+                .identifyPile(*identifier)
+                .getTopCard()
+                .is_some_and(|card| card.isFaceUp && self.mouseOver(ctx, card.x, card.y))
+        });
+        let Some(from) = source else {
+            return;
+        };
+        let card = self.engine.identifyPile(from).getTopCard().cloned();
+        if let Some(action) = card
+            .and_then(|card| self.engine.foundationFor(&card))
+            .and_then(|to| Move::transfer(from, to, 1))
+        {
+            self.apply(action);
+        }
+    }
+
+    /// Moves every card that is safe to move to the final piles
+    fn sendSafeCardsToFoundation(&mut self) {
+        while let Some(action) = self.engine.nextSafeFoundationMove() {
+            self.apply(action);
+        }
+    }
+
+    /// Carries out Enter on the keyboard cursor: moves the picked up cards to the pile of the cursor,
+    /// or else uses the deck, flips a face-down card or picks up the cards the cursor covers
+    fn confirmCursor(&mut self) {
+        let cursor = self.cursor.get_or_insert_with(Cursor::new);
+        if let Some(action) = cursor.confirm(&self.engine) {
+            self.apply(action);
+        }
+    }
+
+    /// Carries out F on the keyboard cursor: moves a picked up card to its final pile,
+    /// or else moves the cursor to the next final pile
+    fn cursorToFoundation(&mut self) {
+        let cursor = self.cursor.get_or_insert_with(Cursor::new);
+        if let Some(action) = cursor.toFoundation(&self.engine) {
+            self.apply(action);
+        }
+    }
+
+    /// Handles the keys of the keyboard cursor, showing the cursor if it is hidden
+    fn cursorKey(&mut self, keycode: KeyCode) {
+        let digit = match keycode {
+            KeyCode::Key1 | KeyCode::Numpad1 => Some(0),
+            KeyCode::Key2 | KeyCode::Numpad2 => Some(1),
+            KeyCode::Key3 | KeyCode::Numpad3 => Some(2),
+            KeyCode::Key4 | KeyCode::Numpad4 => Some(3),
+            KeyCode::Key5 | KeyCode::Numpad5 => Some(4),
+            KeyCode::Key6 | KeyCode::Numpad6 => Some(5),
+            KeyCode::Key7 | KeyCode::Numpad7 => Some(6),
+            _ => None,
+        };
+        let cursorKeys = [
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::D,
+            KeyCode::F,
+            KeyCode::Return,
+            KeyCode::NumpadEnter,
+            KeyCode::Space,
+        ];
+        if digit.is_none() && !cursorKeys.contains(&keycode) {
+            return;
+        }
+        let cursor = self.cursor.get_or_insert_with(Cursor::new);
+        match (keycode, digit) {
+            (_, Some(index)) => cursor.toPile(&self.engine, PILE_IDENTIFIERS[index]),
+            (KeyCode::Left, _) => cursor.moveHorizontally(false),
+            (KeyCode::Right, _) => cursor.moveHorizontally(true),
+            (KeyCode::Up, _) => cursor.moveUp(&self.engine),
+            (KeyCode::Down, _) => cursor.moveDown(&self.engine),
+            (KeyCode::D, _) => cursor.toggleDeck(),
+            (KeyCode::F, _) => self.cursorToFoundation(),
+            (KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space, _) => self.confirmCursor(),
+            _ => {}
+        }
+    }
+
+    /// Draws a ring around an area of the table
+    fn drawRing(&self, ctx: &Context, canvas: &mut Canvas, area: Rect, color: Color) -> GameResult<()> {
+        let ring = Rect::new(
+            area.x - RING_MARGIN,
+            area.y - RING_MARGIN,
+            area.w + 2.0 * RING_MARGIN,
+            area.h + 2.0 * RING_MARGIN,
+        );
+        let mesh = Mesh::new_rounded_rectangle(ctx, DrawMode::stroke(3.0), ring, 6.0, color)?;
+        canvas.draw(&mesh, graphics::DrawParam::default());
+        Ok(())
+    }
+
+    /// Draws the focus ring of the keyboard cursor and the ring around the picked up cards
+    fn drawCursor(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let Some(cursor) = &self.cursor else {
+            return Ok(());
+        };
+        if let Some((pile, count)) = &cursor.selected {
+            let area = self.layout.area(&self.engine, *pile, *count);
+            self.drawRing(ctx, canvas, area, SELECTED_COLOR)?;
+        }
+        let area = self.layout.area(&self.engine, cursor.pile, cursor.count);
+        self.drawRing(ctx, canvas, area, CURSOR_COLOR)
+    }
+
+    /// Stops a running auto-complete
+    fn stopAutoComplete(&mut self) {
+        if self.autoCompleting {
+            self.autoCompleteStopped = true;
+        }
+        self.autoCompleting = false;
+    }
+
+    /// Applies the next move of the auto-complete once its time has come
+    fn stepAutoComplete(&mut self, seconds: f32) {
+        if !self.autoCompleting
+            && self.autoCompleteAutomatically
+            && !self.autoCompleteStopped
+            && !self.mouseDown
+            && self.engine.canAutoComplete()
+        {
+            self.autoCompleting = true;
+            self.autoCompleteTimer = 0.0;
+        }
+        if !self.autoCompleting {
+            return;
+        }
+        self.autoCompleteTimer -= seconds;
+        if self.autoCompleteTimer > 0.0 {
+            return;
+        }
+        match self.engine.nextAutoCompleteMove() {
+            Some(action) => {
+                self.apply(action);
+                self.autoCompleteTimer = AUTO_COMPLETE_SECONDS;
             }
-            _ => false,
+            None => self.autoCompleting = false,
         }
     }
 
-    /// A function that removes all cards from the discard pile, flips them and then returns them in the deck pile
-    fn emptyDiscard(&mut self) {
-        let mut cards = self.discard.removeCards();
-        for card in cards.iter_mut() {
-            card.isFaceUp = false;
-            self.deck.addNoOffset(card.clone());
+    /// Returns the text of the HUD: the clock, the counters and the score
+    fn hudLine(&self) -> String {
+        let time = stats::formatTime(self.engine.elapsed());
+        let pass = self.engine.recycles() + 1;
+        let passes = match self.engine.scoring().maxRecycles(self.engine.difficulty()) {
+            Some(max) => format!("{} of {}", pass, max + 1),
+            None => pass.to_string(),
+        };
+        let score = self.engine.finalScore();
+        let mut line = format!("Time: {}    Moves: {}    Pass: {}    ", time, self.engine.moves(), passes);
+        line += &match self.engine.scoring() {
+            Scoring::Standard => format!("Score: {}", score),
+            Scoring::Vegas => format!("Score: ${}", score),
+        };
+        if let Some(bankroll) = self.bankroll {
+            line += &format!("    Bankroll: ${}", bankroll + score);
         }
+        line
+    }
+
+    /// Draws the HUD in the bottom right corner
+    fn drawHud(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let mut text = Text::new(self.hud.as_str());
+        text.set_scale(24.0);
+        let width = text.measure(ctx)?.x;
+        let position = [self.layout.width - width - HUD_MARGIN, self.layout.messageY()];
+        canvas.draw(&text, graphics::DrawParam::default().dest(position));
+        Ok(())
     }
 
-    /// A function that handles card discarding.
-    fn discardCard(&mut self) {
-        if self.difficulty == Easy {
-            let mut card = self.deck.removeCard();
-            card.as_mut().unwrap().isFaceUp = true;
-            self.discard.addNoOffset(card.clone().unwrap());
+    /// Shows the best hint, or the next one if a hint is already shown
+    fn nextHint(&mut self) {
+        if self.hintIndex.is_none() && self.hintMessage.is_none() {
+            self.hints = hint::hints(&self.engine);
+        }
+        if self.hints.is_empty() {
+            self.hintMessage = Some(if hint::canDraw(&self.engine) {
+                DRAW_MESSAGE
+            } else {
+                STUCK_MESSAGE
+            });
         } else {
-            let mut iterations: usize = 0;
+            self.hintIndex = Some(self.hintIndex.map_or(0, |index| (index + 1) % self.hints.len()));
+        }
+    }
+
+    /// Hides the hint (the position has changed, so it may not apply anymore)
+    fn clearHint(&mut self) {
+        self.hints.clear();
+        self.hintIndex = None;
+        self.hintMessage = None;
+        self.notation = None;
+    }
+
+    /// Shows the position in the text notation, or hides it if it is shown
+    fn toggleNotation(&mut self) {
+        self.notation = match self.notation {
+            Some(_) => None,
+            None => Some(notation::format(&self.engine)),
+        };
+    }
+
+    /// Draws the position in the text notation above the message line, wrapped to the width of the window,
+    /// so that it can go into a bug report or be loaded with --position
+    fn drawNotation(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let Some(notation) = &self.notation else {
+            return Ok(());
+        };
+        let mut text = Text::new(notation.as_str());
+        text.set_scale(NOTATION_SCALE)
+            .set_bounds([self.layout.width - 2.0 * HUD_MARGIN, f32::INFINITY])
+            .set_wrap(true);
+        let size = text.measure(ctx)?;
+        let top = self.layout.messageY() - size.y - HUD_MARGIN;
+        let panel = Rect::new(HUD_MARGIN / 2.0, top - HUD_MARGIN / 2.0, size.x + HUD_MARGIN, size.y + HUD_MARGIN);
+        let background = Mesh::new_rectangle(ctx, DrawMode::fill(), panel, NOTATION_BACKGROUND)?;
+        canvas.draw(&background, graphics::DrawParam::default());
+        canvas.draw(&text, graphics::DrawParam::default().dest([HUD_MARGIN, top]));
+        Ok(())
+    }
+
+    /// Returns the move the shown hint points at
+    fn shownHint(&self) -> Option<&Move> {
+        self.hintIndex.and_then(|index| self.hints.get(index))
+    }
+
+    /// Returns the number of top cards of a pile the shown hint asks to move or flip
+    fn hintedCards(&self, identifier: &PileIdentifier) -> usize {
+        match self.shownHint() {
+            Some(Move::Flip(pile)) if pile == identifier => 1,
+            Some(action) if action.source().as_ref() == Some(identifier) => action.count(),
+            None if self.hintMessage == Some(DRAW_MESSAGE) && *identifier == Deck => 1,
+            _ => 0,
+        }
+    }
+
+    /// Highlights the pile the shown hint moves the cards to
+    fn drawHintDestination(&self, canvas: &mut Canvas) {
+        let Some(identifier) = self.shownHint().and_then(Move::destination) else {
+            return;
+        };
+        let pile = self.engine.identifyPile(identifier);
+        match pile.getTopCard() {
+            Some(card) => self.assets.drawCard(canvas, card, HINT_TINT),
+            None => self.assets.drawFrame(canvas, pile.x, pile.y, HINT_TINT),
+        }
+    }
 
-            while iterations < 3 && !self.deck.empty() {
-                let mut card = self.deck.removeCard();
-                card.as_mut().unwrap().isFaceUp = true;
-                self.discard.addNoOffset(card.clone().unwrap());
-                iterations = iterations + 1;
+    /// Returns the pile the grabbed cards were taken from
+    fn grabbedFrom(&self) -> Option<PileIdentifier> {
+        self.grabbedCards.getCardByIndex(0).map(|card| card.initialPile)
+    }
+
+    /// Draws a pile from the engine, leaving out the cards that are currently grabbed or on their way
+    fn drawPile(&self, canvas: &mut Canvas, identifier: PileIdentifier) {
+        let pile = self.engine.identifyPile(identifier);
+        let grabbed = if self.grabbedFrom().as_ref() == Some(&identifier) {
+            self.grabbedCards.size()
+        } else {
+            0
+        };
+        let visible = pile.size() - grabbed;
+        let tinted = visible - self.hintedCards(&identifier).min(visible);
+        for (index, card) in pile.cards[..visible].iter().enumerate() {
+            if self.animator.isFlying(card) {
+                continue;
             }
+            let tint = if index >= tinted { HINT_TINT } else { Color::WHITE };
+            self.assets.drawCard(canvas, card, tint);
+        }
+    }
+
+    /// Returns the position of the mouse in table coordinates
+    fn mousePosition(&self, ctx: &Context) -> (f32, f32) {
+        let position = ctx.mouse.position();
+        self.layout.toTable(position.x, position.y)
+    }
+
+    /// Checks if the mouse is over a card with coordinates x and y
+    fn mouseOver(&self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        let (mouseX, mouseY) = self.mousePosition(ctx);
+        mouseX > x && mouseX < x + self.layout.cardWidth && mouseY > y && mouseY < y + self.layout.cardHeight
+    }
+
+    /// Checks if the mouse is over a pile but not over the top card
+    fn mouseIsOverPile(&self, ctx: &mut Context, pile: &Pile) -> bool {
+        let (mouseX, mouseY) = self.mousePosition(ctx);
+        let topY = pile.getTopCard().map_or(pile.y, |card| card.y);
+        mouseX > pile.x && mouseX < pile.x + self.layout.cardWidth && mouseY > pile.y && mouseY < topY
+    }
+
+    /// Returns the index of the card of a fanned pile whose uncovered part is under the mouse
+    fn cardUnderMouse(&self, ctx: &mut Context, pile: &Pile) -> Option<usize> {
+        let (_, mouseY) = self.mousePosition(ctx);
+        pile.cards.iter().rposition(|card| card.y < mouseY)
+    }
+
+    /// Copies the top `count` cards of a pile into grabbedCards
+    fn grab(&mut self, identifier: PileIdentifier, count: usize) {
+        let pile = self.engine.identifyPile(identifier);
+        for card in pile.cards[pile.size() - count..].iter() {
+            self.grabbedCards.addToGrabbed(*card);
         }
     }
 }
 
 impl EventHandler for State {
-    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
-        if self.gameOver {
-            return Ok(());
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        if self.focused {
+            self.engine.tick(ctx.time.delta());
+        }
+        let seconds = ctx.time.delta().as_secs_f32();
+        self.animator.update(seconds, &self.layout);
+        self.stepAutoComplete(seconds);
+        if self.engine.isGameOver() && !self.animator.isCascading() && !self.animator.isBusy() {
+            let finals = FINAL_IDENTIFIERS
+                .into_iter()
+                .map(|identifier| self.engine.identifyPile(identifier).cards.clone())
+                .collect();
+            self.animator.startCascade(finals);
+            self.victory = Some(Victory::new(&self.engine));
         }
-        self.checkForWin();
+        self.hud = self.hudLine();
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
-        let mut canvas = graphics::Canvas::from_frame(ctx, Color::from_rgb(0, 128, 0));
+        let mut canvas = graphics::Canvas::from_frame(ctx, self.assets.theme.tableColor());
+        canvas.set_screen_coordinates(self.layout.screen());
 
         //Fixes blurry sprites
         let sampler = Sampler::nearest_clamp();
         canvas.set_sampler(sampler);
 
-        self.deck.drawPile(&mut canvas);
-        self.discard.drawPile(&mut canvas);
+        self.drawPile(&mut canvas, Deck);
+        self.drawPile(&mut canvas, Discard);
 
-        canvas.draw(
-            &self.assets.frame,
-            graphics::DrawParam::default().dest([FINAL1_X, FINAL_Y]),
-        );
-        canvas.draw(
-            &self.assets.frame,
-            graphics::DrawParam::default().dest([FINAL2_X, FINAL_Y]),
-        );
-        canvas.draw(
-            &self.assets.frame,
-            graphics::DrawParam::default().dest([FINAL3_X, FINAL_Y]),
-        );
-        canvas.draw(
-            &self.assets.frame,
-            graphics::DrawParam::default().dest([FINAL4_X, FINAL_Y]),
-        );
+        for identifier in FINAL_IDENTIFIERS {
+            let pile = self.engine.identifyPile(identifier);
+            self.assets.drawFrame(&mut canvas, pile.x, pile.y, Color::WHITE);
+        }
 
-        self.final1.drawPile(&mut canvas);
-        self.final2.drawPile(&mut canvas);
-        self.final3.drawPile(&mut canvas);
-        self.final4.drawPile(&mut canvas);
+        for identifier in FINAL_IDENTIFIERS {
+            self.drawPile(&mut canvas, identifier);
+        }
 
-        self.pile1.drawPile(&mut canvas);
-        self.pile2.drawPile(&mut canvas);
-        self.pile3.drawPile(&mut canvas);
-        self.pile4.drawPile(&mut canvas);
-        self.pile5.drawPile(&mut canvas);
-        self.pile6.drawPile(&mut canvas);
-        self.pile7.drawPile(&mut canvas);
+        for identifier in PILE_IDENTIFIERS {
+            self.drawPile(&mut canvas, identifier);
+        }
 
-        // Prints grabbedCards
-        let mut offset = 0.0;
-        for card in self.grabbedCards.cards.iter().rev() {
+        self.drawHintDestination(&mut canvas);
+        self.animator.draw(&self.assets, &mut canvas);
+        self.drawCursor(ctx, &mut canvas)?;
+        self.drawHud(ctx, &mut canvas)?;
+        self.drawNotation(ctx, &mut canvas)?;
+        let autoCompleteMessage =
+            (!self.autoCompleting && self.engine.canAutoComplete()).then_some(AUTO_COMPLETE_MESSAGE);
+        if let Some(message) = self.hintMessage.or(autoCompleteMessage) {
             canvas.draw(
-                &card.face,
-                graphics::DrawParam::default().dest([card.x, card.y + offset]),
+                Text::new(message).set_scale(24.0),
+                graphics::DrawParam::default().dest([self.engine.identifyPile(Deck).x, self.layout.messageY()]),
             );
-            offset += CARD_OFFSET;
+        }
+
+        // Prints grabbedCards
+        for (index, card) in self.grabbedCards.cards.iter().enumerate() {
+            let mut card = *card;
+            card.y += index as f32 * self.grabbedCards.offset;
+            self.assets.drawCard(&mut canvas, &card, Color::WHITE);
         }
 
         canvas.finish(ctx)?;
+
+        // The overlay is laid out like the other screens
+        if let Some(victory) = &self.victory {
+            let mut overlay = graphics::Canvas::from_frame(ctx, None);
+            overlay.set_screen_coordinates(ui::Screen::fromContext(ctx).coordinates());
+            victory.draw(ctx, &mut overlay)?;
+            overlay.finish(ctx)?;
+        }
         Ok(())
     }
 
     fn mouse_button_down_event(
         &mut self,
         ctx: &mut Context,
-        _button: MouseButton,
-        _x: f32,
-        _y: f32,
+        button: MouseButton,
+        x: f32,
+        y: f32,
     ) -> GameResult<()> {
-        self.mouseDown = true;
+        if let Some(victory) = &self.victory {
+            if button == MouseButton::Left {
+                let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+                self.transition = victory.click(x, y);
+            }
+            return Ok(());
+        }
+        if self.autoCompleting || !self.grabbedCards.empty() {
+            return Ok(());
+        }
+        self.cursor = None;
+        let (x, y) = self.layout.toTable(x, y);
+        match button {
+            MouseButton::Left => self.mouseDown = true,
+            MouseButton::Right => {
+                self.sendSafeCardsToFoundation();
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
 
-        if self.mouseOver(ctx, DECK_X, DECK_Y) && self.grabbedCards.empty() {
-            if !self.deck.empty() {
-                self.discardCard();
+        let deck = self.engine.identifyPile(Deck);
+        if self.mouseOver(ctx, deck.x, deck.y) && self.grabbedCards.empty() {
+            if self.engine.identifyPile(Deck).empty() {
+                self.apply(Move::RecycleDiscard);
             } else {
-                self.emptyDiscard();
+                self.apply(Move::DrawFromDeck);
             }
             return Ok(());
         }
 
+        if self.isDoubleClick(x, y) {
+            self.sendToFoundation(ctx);
+            return Ok(());
+        }
+
         for identifier in PILE_IDENTIFIERS {
-            let currentPile = self.identifyPile(identifier.clone());
-            if !currentPile.empty()
-                && self.mouseOver(
-                    ctx,
-                    currentPile.x,
-                    currentPile.y + currentPile.size() as f32 * CARD_OFFSET,
-                )
-                && !currentPile.getTopCard().unwrap().isFaceUp
-            {
-                self.identifyPileMut(identifier.clone()).flipTopCard();
-                break;
+            let currentPile = self.engine.identifyPile(identifier);
+            if let Some(topCard) = currentPile.getTopCard() {
+                if !topCard.isFaceUp && self.mouseOver(ctx, topCard.x, topCard.y) {
+                    self.apply(Move::Flip(identifier));
+                    break;
+                }
             }
         }
 
@@ -383,51 +696,42 @@ impl EventHandler for State {
     fn mouse_button_up_event(
         &mut self,
         ctx: &mut Context,
-        _button: MouseButton,
+        button: MouseButton,
         _x: f32,
         _y: f32,
     ) -> GameResult<()> {
-        if self.mouseDown && !self.grabbedCards.empty() {
+        if button != MouseButton::Left {
+            return Ok(());
+        }
+        if let (true, Some(from)) = (self.mouseDown, self.grabbedFrom()) {
+            let count = self.grabbedCards.size();
+            let baseCard = self.grabbedCards.cards[0];
+            let mut receiver = None;
+
             for pileIdentifier in PILE_IDENTIFIERS {
-                let currentPile = self.identifyPile(pileIdentifier.clone()).clone();
-                let pileSize = currentPile.size() as f32;
-                let lastCard = self.grabbedCards.cards.last();
-                if lastCard.is_some()
-                    && self.mouseOver(ctx, currentPile.x, PILE_Y + pileSize * CARD_OFFSET)
-                    && self.canBeStacked(lastCard.unwrap().clone(), pileIdentifier.clone())
+                let pile = self.engine.identifyPile(pileIdentifier);
+                if self.mouseOver(ctx, pile.x, pile.nextY())
+                    && self.engine.canBeStacked(&baseCard, pileIdentifier)
                 {
-                    for card in self.grabbedCards.cards.clone().iter().rev() {
-                        let pile = self.identifyPileMut(pileIdentifier.clone());
-                        pile.addCard(card.clone());
-                    }
-                    self.grabbedCards.cards.clear();
-                    break;
-                }
-            }
-            for finalIdentifier in FINAL_IDENTIFIERS {
-                let lastCard = self.grabbedCards.cards.last();
-                if lastCard.is_some()
-                    && self.canBeStacked(lastCard.unwrap().clone(), finalIdentifier.clone())
-                {
-                    for card in self.grabbedCards.cards.clone().iter().rev() {
-                        let pile = self.identifyPileMut(finalIdentifier.clone());
-                        pile.addNoOffset(card.clone());
-                    }
-
-                    self.grabbedCards.cards.clear();
+                    receiver = Some(pileIdentifier);
                     break;
                 }
             }
-            for card in self.grabbedCards.cards.clone().iter().rev() {
-                let initialPile = self.identifyPileMut(card.clone().initialPile);
-                match card.initialPile {
-                    Discard | Final1 | Final2 | Final3 | Final4 => {
-                        initialPile.addNoOffset(card.clone())
-                    }
-                    _ => initialPile.addCard(card.clone()),
-                }
+            if receiver.is_none() {
+                receiver = FINAL_IDENTIFIERS.into_iter().find(|identifier| {
+                    let pile = self.engine.identifyPile(*identifier);
+                    self.mouseOver(ctx, pile.x, pile.y) && self.engine.canBeStacked(&baseCard, *identifier)
+                });
             }
+            let before = self.visibleCards();
+            let moved = receiver
+                .and_then(|to| Move::transfer(from, to, count))
+                .is_some_and(|action| self.apply(action));
             self.grabbedCards.cards.clear();
+            if !moved {
+                // The cards slide back to where they were taken from
+                self.animator.moveCards(&before, &self.tableCards());
+            }
         }
 
         self.mouseDown = false;
@@ -442,56 +746,123 @@ impl EventHandler for State {
         _dx: f32,
         _dy: f32,
     ) -> GameResult<()> {
-        if self.mouseDown && self.grabbedCards.empty() {
+        if let Some(victory) = self.victory.as_mut() {
+            let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+            victory.hover(x, y);
+            return Ok(());
+        }
+        if self.mouseDown && self.grabbedCards.empty() && !self.autoCompleting {
             for pileIdentifier in PILE_IDENTIFIERS {
-                let pile = self.identifyPile(pileIdentifier.clone()).clone();
-                let topCard = pile.getTopCard();
-                let mouseIsOverCurrentPile = self.mouseIsOverPile(ctx, pile.clone());
-                let mouseOverTopCard = topCard.is_some()
-                    && self.mouseOver(ctx, topCard.unwrap().x, topCard.unwrap().y);
-
-                if topCard.is_some() && (mouseIsOverCurrentPile || mouseOverTopCard) {
+                let pile = self.engine.identifyPile(pileIdentifier);
+                let Some(topCard) = pile.getTopCard() else {
+                    continue;
+                };
+                let mouseIsOverCurrentPile = self.mouseIsOverPile(ctx, pile);
+                let mouseOverTopCard = self.mouseOver(ctx, topCard.x, topCard.y);
+
+                if mouseIsOverCurrentPile || mouseOverTopCard {
                     if mouseOverTopCard {
-                        let pile = self.identifyPileMut(pileIdentifier.clone());
-                        let removedCard = pile.removeCard().unwrap();
-                        self.grabbedCards.addToGrabbed(removedCard.clone());
+                        if topCard.isFaceUp {
+                            self.grab(pileIdentifier, 1);
+                        }
                     } else {
-                        let cardIndex = ((ctx.mouse.position().y - PILE_Y) / CARD_OFFSET) as usize;
-                        let pile = self.identifyPile(pileIdentifier.clone()).clone();
-                        let card = pile.getCardByIndex(cardIndex).clone();
+                        let cardIndex = self.cardUnderMouse(ctx, pile).unwrap_or(0);
+                        let card = pile.getCardByIndex(cardIndex);
 
                         if card.is_some() && card.unwrap().isFaceUp {
-                            let pileSize = pile.size();
-                            for _ in cardIndex..pileSize {
-                                let pile = self.identifyPileMut(pileIdentifier.clone());
-                                let card = pile.removeCard();
-                                self.grabbedCards.addToGrabbed(card.unwrap());
-                            }
+                            let count = pile.size() - cardIndex;
+                            self.grab(pileIdentifier, count);
                         }
                     }
                     break;
                 }
             }
             for finalIdentifier in FINAL_IDENTIFIERS {
-                let currentPile = self.identifyPile(finalIdentifier.clone()).clone();
-                if currentPile.getTopCard().is_some()
+                let currentPile = self.engine.identifyPile(finalIdentifier);
+                if self.grabbedCards.empty()
+                    && currentPile.getTopCard().is_some()
                     && self.mouseOver(ctx, currentPile.x, currentPile.y)
                 {
-                    let pile = self.identifyPileMut(finalIdentifier.clone());
-                    let card = pile.removeCard().unwrap();
-                    self.grabbedCards.addToGrabbed(card);
+                    self.grab(finalIdentifier, 1);
                     break;
                 }
             }
-            if self.discard.getTopCard().is_some() && self.mouseOver(ctx, DISCARD_X, DISCARD_Y) {
-                self.grabbedCards
-                    .addToGrabbed(self.discard.removeCard().unwrap());
+            let discard = self.engine.identifyPile(Discard);
+            if self.grabbedCards.empty()
+                && discard.getTopCard().is_some()
+                && self.mouseOver(ctx, discard.x, discard.y)
+            {
+                self.grab(Discard, 1);
             }
         }
+        let (x, y) = self.layout.toTable(x, y);
         for card in self.grabbedCards.cards.iter_mut() {
-            card.x = x - CARD_WIDTH / 2.0; // CARD_WIDTH / 2.0
-            card.y = y - CARD_HEIGHT / 2.0; // CARD_HEIGHT / 2.0
+            card.x = x - self.layout.cardWidth / 2.0;
+            card.y = y - self.layout.cardHeight / 2.0;
+        }
+        Ok(())
+    }
+
+    fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) -> GameResult<()> {
+        self.layout = Layout::new(width, height, &self.assets.theme);
+        self.assets.setScale(self.layout.scale);
+        self.layout.place(&mut self.engine);
+        // The cards on their way would land where the piles used to be
+        self.animator.skip();
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, _repeated: bool) -> GameResult<()> {
+        let selected = self.cursor.as_ref().is_some_and(|cursor| cursor.selected.is_some());
+        match input.keycode {
+            Some(KeyCode::Escape) if selected => {
+                if let Some(cursor) = self.cursor.as_mut() {
+                    cursor.selected = None;
+                }
+            }
+            Some(KeyCode::Escape) => {
+                self.save();
+                self.transition = Transition::Menu;
+            }
+            Some(keycode) if self.victory.is_some() => {
+                if let Some(victory) = self.victory.as_mut() {
+                    self.transition = victory.key(keycode);
+                }
+            }
+            Some(KeyCode::Z) if input.mods.contains(KeyMods::CTRL) && self.grabbedCards.empty() => {
+                self.rewind(false);
+            }
+            Some(KeyCode::Y) if input.mods.contains(KeyMods::CTRL) && self.grabbedCards.empty() => {
+                self.rewind(true);
+            }
+            Some(KeyCode::A) if self.grabbedCards.empty() && self.engine.canAutoComplete() => {
+                self.clearHint();
+                self.autoCompleting = true;
+                self.autoCompleteTimer = 0.0;
+            }
+            Some(KeyCode::H) if self.grabbedCards.empty() && !self.autoCompleting => self.nextHint(),
+            Some(KeyCode::P) if input.mods.is_empty() => self.toggleNotation(),
+            Some(keycode) if self.grabbedCards.empty() && !self.autoCompleting && input.mods.is_empty() => {
+                self.cursorKey(keycode)
+            }
+            _ => {}
         }
         Ok(())
     }
+
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) -> GameResult<()> {
+        self.focused = gained;
+        Ok(())
+    }
+
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        self.save();
+        Ok(false)
+    }
+}
+
+impl Scene for State {
+    fn transition(&mut self) -> Transition {
+        std::mem::take(&mut self.transition)
+    }
 }
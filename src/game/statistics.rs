@@ -0,0 +1,105 @@
+#![allow(non_snake_case)]
+
+use crate::game::scene::{Scene, Transition};
+use crate::game::stats::{ModeStats, PlayerStats, MODES};
+use crate::game::ui::{self, Button};
+
+use ggez::event::{EventHandler, MouseButton};
+use ggez::graphics::{Canvas, Color, DrawParam, Text};
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::{Context, GameResult};
+
+/// The y coordinate of the title
+const TITLE_Y: f32 = 80.0;
+/// The y coordinate of the back button
+const BACK_Y: f32 = 580.0;
+/// The y coordinate of the row with the names of the modes
+const HEADER_Y: f32 = 170.0;
+/// The vertical distance between two rows of the table
+const ROW_HEIGHT: f32 = 34.0;
+/// The x coordinate of the labels of the table
+const LABEL_X: f32 = 150.0;
+/// The x coordinate of the first column of the table
+const FIRST_COLUMN_X: f32 = 420.0;
+/// The horizontal distance between two columns of the table
+const COLUMN_WIDTH: f32 = 200.0;
+
+/// The statistics screen
+pub struct Statistics {
+    /// The statistics of every mode, in the order of the columns
+    modes: Vec<ModeStats>,
+    /// Returns to the start screen
+    back: Button,
+    /// The color of the table of the theme
+    background: Color,
+    /// The transition requested by the last event
+    transition: Transition,
+}
+
+impl Statistics {
+    /// Creates the statistics screen on the table color of the theme
+    pub fn new(background: Color) -> Statistics {
+        let stats = PlayerStats::load();
+        Statistics {
+            modes: MODES.into_iter().map(|(difficulty, scoring)| stats.mode(difficulty, scoring)).collect(),
+            back: Button::centered("Back", BACK_Y),
+            background,
+            transition: Transition::None,
+        }
+    }
+}
+
+impl EventHandler for Statistics {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let mut canvas = Canvas::from_frame(ctx, self.background);
+        canvas.set_screen_coordinates(ui::Screen::fromContext(ctx).coordinates());
+        ui::drawCentered(ctx, &mut canvas, "Statistics", TITLE_Y, 56.0)?;
+        let mut cell = |text: &str, x: f32, y: f32| {
+            canvas.draw(Text::new(text).set_scale(24.0), DrawParam::default().dest([x, y]));
+        };
+        for (column, mode) in self.modes.iter().enumerate() {
+            let x = FIRST_COLUMN_X + column as f32 * COLUMN_WIDTH;
+            cell(&mode.name(), x, HEADER_Y);
+            for (row, (label, value)) in mode.rows().into_iter().enumerate() {
+                let y = HEADER_Y + (row + 1) as f32 * ROW_HEIGHT;
+                if column == 0 {
+                    cell(label, LABEL_X, y);
+                }
+                cell(&value, x, y);
+            }
+        }
+        self.back.draw(ctx, &mut canvas, true)?;
+        canvas.finish(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        _button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult<()> {
+        let (x, y) = ui::Screen::fromContext(ctx).toScreen(x, y);
+        if self.back.contains(x, y) {
+            self.transition = Transition::Menu;
+        }
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, _repeated: bool) -> GameResult<()> {
+        if let Some(KeyCode::Escape | KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space) = input.keycode {
+            self.transition = Transition::Menu;
+        }
+        Ok(())
+    }
+}
+
+impl Scene for Statistics {
+    fn transition(&mut self) -> Transition {
+        std::mem::take(&mut self.transition)
+    }
+}
@@ -0,0 +1,246 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::scoring::Scoring;
+use crate::game::difficulty::Difficulty;
+use crate::game::paths;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::time::Duration;
+use std::{fs, io};
+
+/// The modes statistics are kept for, in the order they are shown
+pub const MODES: [(Difficulty, Scoring); 4] = [
+    (Difficulty::Easy, Scoring::Standard),
+    (Difficulty::Hard, Scoring::Standard),
+    (Difficulty::Easy, Scoring::Vegas),
+    (Difficulty::Hard, Scoring::Vegas),
+];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+/// The statistics of one draw mode and rule set
+pub struct ModeStats {
+    pub difficulty: Difficulty,
+    pub scoring: Scoring,
+    /// The number of games that were won or abandoned
+    pub played: u32,
+    pub won: u32,
+    /// The number of games won in a row up to the last game
+    pub currentStreak: u32,
+    pub longestStreak: u32,
+    /// The fastest win, in milliseconds
+    pub bestTimeMillis: Option<u64>,
+    /// The sum of the times of all wins, in milliseconds
+    pub totalWinTimeMillis: u64,
+    /// The win with the fewest moves
+    pub fewestMoves: Option<u32>,
+    /// The highest score of a win
+    pub bestScore: Option<i32>,
+}
+
+impl ModeStats {
+    /// Creates empty statistics for a mode
+    pub fn new(difficulty: Difficulty, scoring: Scoring) -> ModeStats {
+        ModeStats {
+            difficulty,
+            scoring,
+            played: 0,
+            won: 0,
+            currentStreak: 0,
+            longestStreak: 0,
+            bestTimeMillis: None,
+            totalWinTimeMillis: 0,
+            fewestMoves: None,
+            bestScore: None,
+        }
+    }
+
+    /// Returns the name of the mode, e.g. "Draw 3, Vegas"
+    pub fn name(&self) -> String {
+        let draw = match self.difficulty {
+            Difficulty::Easy => "Draw 1",
+            Difficulty::Hard => "Draw 3",
+        };
+        format!("{}, {}", draw, self.scoring)
+    }
+
+    /// Returns the percentage of games that were won
+    pub fn winRate(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.won as f64 * 100.0 / self.played as f64
+        }
+    }
+
+    /// Returns the fastest win
+    pub fn bestTime(&self) -> Option<Duration> {
+        self.bestTimeMillis.map(Duration::from_millis)
+    }
+
+    /// Returns the average time of a win
+    pub fn averageTime(&self) -> Option<Duration> {
+        (self.won > 0).then(|| Duration::from_millis(self.totalWinTimeMillis / self.won as u64))
+    }
+
+    /// Adds a won game
+    fn recordWin(&mut self, engine: &Engine) {
+        let millis = engine.elapsed().as_millis() as u64;
+        self.played += 1;
+        self.won += 1;
+        self.currentStreak += 1;
+        self.longestStreak = self.longestStreak.max(self.currentStreak);
+        self.bestTimeMillis = Some(self.bestTimeMillis.map_or(millis, |best| best.min(millis)));
+        self.totalWinTimeMillis += millis;
+        self.fewestMoves = Some(self.fewestMoves.map_or(engine.moves(), |fewest| fewest.min(engine.moves())));
+        let score = engine.finalScore();
+        self.bestScore = Some(self.bestScore.map_or(score, |best| best.max(score)));
+    }
+
+    /// Adds a game that was given up
+    fn recordLoss(&mut self) {
+        self.played += 1;
+        self.currentStreak = 0;
+    }
+
+    /// Formats a score the way the rule set shows it
+    fn formatScore(&self, score: i32) -> String {
+        match self.scoring {
+            Scoring::Standard => score.to_string(),
+            Scoring::Vegas => format!("${}", score),
+        }
+    }
+
+    /// Returns the label and the value of every statistic, in the order they are shown
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        vec![
+            ("Games played", self.played.to_string()),
+            ("Games won", self.won.to_string()),
+            ("Win rate", format!("{:.1}%", self.winRate())),
+            ("Current streak", self.currentStreak.to_string()),
+            ("Longest streak", self.longestStreak.to_string()),
+            ("Best time", optional(self.bestTime().map(formatTime))),
+            ("Average time", optional(self.averageTime().map(formatTime))),
+            ("Fewest moves", optional(self.fewestMoves.map(|moves| moves.to_string()))),
+            ("Best score", optional(self.bestScore.map(|score| self.formatScore(score)))),
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+/// The statistics of every mode, kept across games
+pub struct PlayerStats {
+    pub modes: Vec<ModeStats>,
+}
+
+impl PlayerStats {
+    /// Reads the statistics, starting empty if there are none or they cannot be read
+    pub fn load() -> PlayerStats {
+        paths::statsFile()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the statistics, creating the directory if needed
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = paths::statsFile() else {
+            return Ok(());
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, self.toJson())
+    }
+
+    /// Returns the statistics of a mode
+    pub fn mode(&self, difficulty: Difficulty, scoring: Scoring) -> ModeStats {
+        self.modes
+            .iter()
+            .find(|mode| mode.difficulty == difficulty && mode.scoring == scoring)
+            .cloned()
+            .unwrap_or_else(|| ModeStats::new(difficulty, scoring))
+    }
+
+    /// Returns the statistics of a mode for changing them
+    fn modeMut(&mut self, difficulty: Difficulty, scoring: Scoring) -> &mut ModeStats {
+        let index = match self
+            .modes
+            .iter()
+            .position(|mode| mode.difficulty == difficulty && mode.scoring == scoring)
+        {
+            Some(index) => index,
+            None => {
+                self.modes.push(ModeStats::new(difficulty, scoring));
+                self.modes.len() - 1
+            }
+        };
+        &mut self.modes[index]
+    }
+
+    /// Adds a game that was won (`won`) or abandoned to the statistics on disk.
+    /// Abandoned games without a single move are not counted.
+    pub fn record(engine: &Engine, won: bool) {
+        if !won && engine.moves() == 0 {
+            return;
+        }
+        let mut stats = PlayerStats::load();
+        let mode = stats.modeMut(engine.difficulty(), engine.scoring());
+        if won {
+            mode.recordWin(engine);
+        } else {
+            mode.recordLoss();
+        }
+        if let Err(error) = stats.save() {
+            eprintln!("Could not save the statistics: {}", error);
+        }
+    }
+
+    /// Formats the statistics of every mode as plain text
+    pub fn toText(&self) -> String {
+        let mut text = String::new();
+        for (difficulty, scoring) in MODES {
+            let mode = self.mode(difficulty, scoring);
+            let _ = writeln!(text, "{}", mode.name());
+            for (label, value) in mode.rows() {
+                let _ = writeln!(text, "  {:<16}{}", label, value);
+            }
+            text.push('\n');
+        }
+        text.pop();
+        text
+    }
+
+    /// Formats the statistics of every mode as JSON, including the win rate and the average time
+    pub fn toReport(&self) -> String {
+        let modes: Vec<serde_json::Value> = MODES
+            .into_iter()
+            .map(|(difficulty, scoring)| {
+                let mode = self.mode(difficulty, scoring);
+                let mut value = serde_json::to_value(&mode).expect("statistics can always be serialized");
+                value["name"] = mode.name().into();
+                value["winRate"] = mode.winRate().into();
+                value["averageTimeMillis"] = mode.averageTime().map(|time| time.as_millis() as u64).into();
+                value
+            })
+            .collect();
+        serde_json::to_string_pretty(&serde_json::json!({ "modes": modes }))
+            .expect("statistics can always be serialized")
+    }
+
+    /// Serializes the statistics
+    pub fn toJson(&self) -> String {
+        serde_json::to_string_pretty(self).expect("statistics can always be serialized")
+    }
+}
+
+/// Formats a duration as minutes and seconds, or hours, minutes and seconds
+pub fn formatTime(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
@@ -0,0 +1,183 @@
+#![allow(non_snake_case)]
+
+use crate::game::layout::{DESIGN_HEIGHT, DESIGN_WIDTH};
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+
+/// The width of a menu button
+pub const BUTTON_WIDTH: f32 = 320.0;
+/// The height of a menu button
+pub const BUTTON_HEIGHT: f32 = 50.0;
+/// The vertical distance between the tops of two menu buttons
+pub const BUTTON_SPACING: f32 = 64.0;
+/// The color of a button
+const BUTTON_COLOR: Color = Color::new(0.0, 0.3, 0.0, 1.0);
+/// The color of the selected button
+const SELECTED_COLOR: Color = Color::new(0.0, 0.45, 0.0, 1.0);
+/// The color of the label of a button that cannot be used
+const DISABLED_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.35);
+
+/// How the screens fit in the window. A screen is laid out on an area of the designed table size,
+/// which is scaled like the table of a game to fit the window and centered in it.
+pub struct Screen {
+    /// The number of window pixels per screen unit
+    scale: f32,
+    /// The size of the window in screen units
+    width: f32,
+    height: f32,
+}
+
+impl Screen {
+    /// Fits the screens in the current window
+    pub fn fromContext(ctx: &Context) -> Screen {
+        let (width, height) = ctx.gfx.drawable_size();
+        let (width, height) = (width.max(1.0), height.max(1.0));
+        let scale = (width / DESIGN_WIDTH).min(height / DESIGN_HEIGHT);
+        Screen {
+            scale,
+            width: width / scale,
+            height: height / scale,
+        }
+    }
+
+    /// Returns the window in screen units, to be set as the screen coordinates of a canvas
+    pub fn coordinates(&self) -> Rect {
+        Rect::new(
+            -(self.width - DESIGN_WIDTH) / 2.0,
+            -(self.height - DESIGN_HEIGHT) / 2.0,
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Turns window coordinates (e.g. of the mouse) into screen coordinates
+    pub fn toScreen(&self, x: f32, y: f32) -> (f32, f32) {
+        let area = self.coordinates();
+        (x / self.scale + area.x, y / self.scale + area.y)
+    }
+}
+
+/// A clickable rectangle with a centered label
+pub struct Button {
+    /// The text on the button
+    pub label: String,
+    /// The area of the button
+    pub rect: Rect,
+    /// Indicates if the button can be used
+    pub enabled: bool,
+}
+
+impl Button {
+    /// Creates a button that is horizontally centered on the screen with its top at y
+    pub fn centered(label: &str, y: f32) -> Button {
+        Button {
+            label: label.to_string(),
+            rect: Rect::new((DESIGN_WIDTH - BUTTON_WIDTH) / 2.0, y, BUTTON_WIDTH, BUTTON_HEIGHT),
+            enabled: true,
+        }
+    }
+
+    /// Checks if the button can be used and the point (x, y) is on it
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        self.enabled && self.rect.contains([x, y])
+    }
+
+    /// Draws the button, highlighted if it is selected
+    pub fn draw(&self, ctx: &Context, canvas: &mut Canvas, selected: bool) -> GameResult<()> {
+        let color = if selected && self.enabled { SELECTED_COLOR } else { BUTTON_COLOR };
+        let background = Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), self.rect, 8.0, color)?;
+        canvas.draw(&background, DrawParam::default());
+        if selected && self.enabled {
+            let border = Mesh::new_rounded_rectangle(ctx, DrawMode::stroke(2.0), self.rect, 8.0, Color::WHITE)?;
+            canvas.draw(&border, DrawParam::default());
+        }
+
+        let mut text = Text::new(self.label.as_str());
+        text.set_scale(26.0);
+        let size = text.measure(ctx)?;
+        let labelColor = if self.enabled { Color::WHITE } else { DISABLED_COLOR };
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest([
+                    self.rect.x + (self.rect.w - size.x) / 2.0,
+                    self.rect.y + (self.rect.h - size.y) / 2.0,
+                ])
+                .color(labelColor),
+        );
+        Ok(())
+    }
+}
+
+/// A column of buttons, one for each choice of a screen, with one of them selected
+pub struct ButtonList<T: Copy + PartialEq> {
+    /// The choices and their buttons, top to bottom
+    items: Vec<(T, Button)>,
+    /// The index of the selected choice
+    selected: usize,
+}
+
+impl<T: Copy + PartialEq> ButtonList<T> {
+    /// Creates a column of buttons horizontally centered on the screen, starting at y
+    pub fn new(choices: Vec<(T, String)>, y: f32) -> ButtonList<T> {
+        let items = choices
+            .into_iter()
+            .enumerate()
+            .map(|(index, (item, label))| (item, Button::centered(&label, y + index as f32 * BUTTON_SPACING)))
+            .collect();
+        ButtonList { items, selected: 0 }
+    }
+
+    /// Returns the button of a choice
+    pub fn button(&mut self, choice: T) -> Option<&mut Button> {
+        self.items.iter_mut().find(|(item, _)| *item == choice).map(|(_, button)| button)
+    }
+
+    /// Returns the choice whose button is at (x, y), if it can be used
+    pub fn at(&self, x: f32, y: f32) -> Option<T> {
+        self.items.iter().find(|(_, button)| button.contains(x, y)).map(|(item, _)| *item)
+    }
+
+    /// Selects the button at (x, y), if there is one
+    pub fn hover(&mut self, x: f32, y: f32) {
+        if let Some(index) = self.items.iter().position(|(_, button)| button.contains(x, y)) {
+            self.selected = index;
+        }
+    }
+
+    /// Returns the selected choice, if it can be used
+    pub fn selected(&self) -> Option<T> {
+        let (item, button) = &self.items[self.selected];
+        button.enabled.then_some(*item)
+    }
+
+    /// Moves the selection to the next usable button in the given direction
+    pub fn moveSelection(&mut self, forward: bool) {
+        let count = self.items.len();
+        let mut index = self.selected;
+        for _ in 0..count {
+            index = if forward { (index + 1) % count } else { (index + count - 1) % count };
+            if self.items[index].1.enabled {
+                self.selected = index;
+                return;
+            }
+        }
+    }
+
+    /// Draws all buttons
+    pub fn draw(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        for (index, (_, button)) in self.items.iter().enumerate() {
+            button.draw(ctx, canvas, index == self.selected)?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws a line of text horizontally centered on the screen with its top at y
+pub fn drawCentered(ctx: &Context, canvas: &mut Canvas, line: &str, y: f32, scale: f32) -> GameResult<()> {
+    let mut text = Text::new(line);
+    text.set_scale(scale);
+    let size = text.measure(ctx)?;
+    canvas.draw(&text, DrawParam::default().dest([(DESIGN_WIDTH - size.x) / 2.0, y]));
+    Ok(())
+}
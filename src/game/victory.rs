@@ -0,0 +1,180 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::scoring::Scoring;
+use crate::game::difficulty::Difficulty;
+use crate::game::layout::DESIGN_WIDTH;
+use crate::game::scene::Transition;
+use crate::game::stats::{self, PlayerStats};
+use crate::game::ui::{self, ButtonList};
+
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect};
+use ggez::input::keyboard::KeyCode;
+use ggez::{Context, GameResult};
+
+/// The width of the panel
+const PANEL_WIDTH: f32 = 560.0;
+/// The y coordinate of the top of the panel
+const PANEL_Y: f32 = 70.0;
+/// The height of the panel
+const PANEL_HEIGHT: f32 = 550.0;
+/// The color of the panel (the table shows through)
+const PANEL_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.75);
+/// The y coordinate of the title
+const TITLE_Y: f32 = 95.0;
+/// The y coordinate of the first line of the summary
+const SUMMARY_Y: f32 = 175.0;
+/// The vertical distance between two lines of the summary
+const LINE_HEIGHT: f32 = 30.0;
+/// The y coordinate of the first button
+const FIRST_BUTTON_Y: f32 = 320.0;
+
+#[derive(Clone, Copy, PartialEq)]
+/// The choices offered once a game is won
+enum Item {
+    SameMode,
+    OtherMode,
+    Replay,
+    Quit,
+}
+
+/// The overlay shown over the table once a game is won: a summary of the game and the statistics
+/// of its mode, and the choice of what to play next
+pub struct Victory {
+    /// The buttons of the choices
+    buttons: ButtonList<Item>,
+    /// The lines of the summary
+    summary: Vec<String>,
+    /// The mode and the deal of the game that was won (no deal for a loaded position, which cannot be replayed)
+    difficulty: Difficulty,
+    scoring: Scoring,
+    seed: Option<u64>,
+}
+
+impl Victory {
+    /// Creates the overlay for the game of an engine that was won
+    pub fn new(engine: &Engine) -> Victory {
+        let difficulty = engine.difficulty();
+        let other = match difficulty {
+            Difficulty::Easy => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        };
+        let mut choices = vec![
+            (Item::SameMode, format!("New Game: {}", Self::drawName(difficulty))),
+            (Item::OtherMode, format!("New Game: {}", Self::drawName(other))),
+        ];
+        if let Some(seed) = engine.seed() {
+            choices.push((Item::Replay, format!("Replay Deal #{}", seed)));
+        }
+        choices.push((Item::Quit, "Quit".to_string()));
+
+        let mode = PlayerStats::load().mode(difficulty, engine.scoring());
+        let score = match engine.scoring() {
+            Scoring::Standard => engine.finalScore().to_string(),
+            Scoring::Vegas => format!("${}", engine.finalScore()),
+        };
+        let summary = vec![
+            format!(
+                "Time: {}    Moves: {}    Score: {}",
+                stats::formatTime(engine.elapsed()),
+                engine.moves(),
+                score
+            ),
+            format!(
+                "{}: {} of {} games won ({:.1}%)",
+                mode.name(),
+                mode.won,
+                mode.played,
+                mode.winRate()
+            ),
+            format!(
+                "Current streak: {}    Longest streak: {}",
+                mode.currentStreak, mode.longestStreak
+            ),
+            format!(
+                "Best time: {}    Fewest moves: {}",
+                mode.bestTime().map_or("-".to_string(), stats::formatTime),
+                mode.fewestMoves.map_or("-".to_string(), |moves| moves.to_string())
+            ),
+        ];
+
+        Victory {
+            buttons: ButtonList::new(choices, FIRST_BUTTON_Y),
+            summary,
+            difficulty,
+            scoring: engine.scoring(),
+            seed: engine.seed(),
+        }
+    }
+
+    /// Returns the name of a draw mode as the buttons show it
+    fn drawName(difficulty: Difficulty) -> &'static str {
+        match difficulty {
+            Difficulty::Easy => "Draw 1",
+            Difficulty::Hard => "Draw 3",
+        }
+    }
+
+    /// Returns the transition a choice leads to
+    fn choose(&self, item: Item) -> Transition {
+        let game = |difficulty, seed| Transition::Play(Box::new(Engine::withScoring(difficulty, self.scoring, seed)));
+        match item {
+            Item::SameMode => game(self.difficulty, rand::random()),
+            Item::OtherMode => {
+                let other = match self.difficulty {
+                    Difficulty::Easy => Difficulty::Hard,
+                    Difficulty::Hard => Difficulty::Easy,
+                };
+                game(other, rand::random())
+            }
+            Item::Replay => match self.seed {
+                Some(seed) => game(self.difficulty, seed),
+                None => Transition::None,
+            },
+            Item::Quit => Transition::Quit,
+        }
+    }
+
+    /// Handles a click at (x, y) in screen coordinates
+    pub fn click(&self, x: f32, y: f32) -> Transition {
+        self.buttons.at(x, y).map_or(Transition::None, |item| self.choose(item))
+    }
+
+    /// Selects the button under the mouse at (x, y) in screen coordinates
+    pub fn hover(&mut self, x: f32, y: f32) {
+        self.buttons.hover(x, y);
+    }
+
+    /// Handles a key: the arrows select a button and Enter chooses it,
+    /// N, O, R and Q choose a button directly
+    pub fn key(&mut self, keycode: KeyCode) -> Transition {
+        match keycode {
+            KeyCode::Up => self.buttons.moveSelection(false),
+            KeyCode::Down | KeyCode::Tab => self.buttons.moveSelection(true),
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space => {
+                if let Some(item) = self.buttons.selected() {
+                    return self.choose(item);
+                }
+            }
+            KeyCode::N => return self.choose(Item::SameMode),
+            KeyCode::O => return self.choose(Item::OtherMode),
+            KeyCode::R => return self.choose(Item::Replay),
+            KeyCode::Q => return self.choose(Item::Quit),
+            _ => {}
+        }
+        Transition::None
+    }
+
+    /// Draws the overlay on a canvas in screen coordinates
+    pub fn draw(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let panel = Rect::new((DESIGN_WIDTH - PANEL_WIDTH) / 2.0, PANEL_Y, PANEL_WIDTH, PANEL_HEIGHT);
+        let background = Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), panel, 12.0, PANEL_COLOR)?;
+        canvas.draw(&background, DrawParam::default());
+
+        ui::drawCentered(ctx, canvas, "You won!", TITLE_Y, 56.0)?;
+        for (index, line) in self.summary.iter().enumerate() {
+            ui::drawCentered(ctx, canvas, line, SUMMARY_Y + index as f32 * LINE_HEIGHT, 22.0)?;
+        }
+        self.buttons.draw(ctx, canvas)
+    }
+}
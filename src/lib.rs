@@ -0,0 +1,12 @@
+#![allow(non_snake_case)]
+#![allow(clippy::module_inception)]
+
+#[cfg(feature = "gui")]
+pub mod assets;
+pub mod card;
+pub mod engine;
+pub mod game;
+pub mod script;
+pub mod solver;
+#[cfg(all(feature = "tui", unix))]
+pub mod tui;
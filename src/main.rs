@@ -1,10 +1,7 @@
-#![allow(non_snake_case)]
-
-mod assets;
-mod card;
-mod game;
-use crate::game::game::Game;
-
-fn main() {
-    Game::start();
-}
+#![allow(non_snake_case)]
+
+use solitaire::game::game::Game;
+
+fn main() {
+    Game::start();
+}
@@ -0,0 +1,222 @@
+#![allow(non_snake_case)]
+
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::engine::engine::Engine;
+use crate::engine::error::MoveError;
+use crate::engine::moves::Move;
+use crate::engine::notation;
+use std::fmt;
+
+/// Starts a comment that runs to the end of the line
+const COMMENT: char = '#';
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The pile cards are moved to
+pub enum Target {
+    /// A pile given by name
+    Pile(PileIdentifier),
+    /// Whichever final pile takes the card (`f`)
+    Foundation,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+/// One line of a script
+pub enum Command {
+    /// `d`: draws from the deck, or turns the discard pile over once the deck is empty
+    Draw,
+    /// `<from> <to> [count]`: moves the top `count` cards (1 if not given) of a pile onto another
+    Transfer {
+        from: PileIdentifier,
+        to: Target,
+        count: usize,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug)]
+/// The reason a line of a script could not be carried out
+pub enum CommandError {
+    /// The line is not a command
+    UnknownCommand(String),
+    /// The word does not name a pile
+    UnknownPile(String),
+    /// The number of cards is not a positive number
+    InvalidCount(String),
+    /// No move takes cards between the two piles (e.g. from a pile to the deck)
+    NotAMove(String),
+    /// No final pile takes the top card of the pile
+    NoFoundation(PileIdentifier),
+    /// The engine rejected the move
+    Illegal(MoveError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::UnknownCommand(line) => write!(f, "'{}' is not a command", line),
+            CommandError::UnknownPile(name) => write!(f, "'{}' is not a pile", name),
+            CommandError::InvalidCount(count) => write!(f, "'{}' is not a number of cards", count),
+            CommandError::NotAMove(line) => write!(f, "'{}' does not move any cards", line),
+            CommandError::NoFoundation(pile) => {
+                write!(f, "the top card of {} cannot go to a final pile", notation::pileName(*pile))
+            }
+            CommandError::Illegal(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl Command {
+    /// Parses a line of a script. Returns None for an empty line or a comment.
+    pub fn parse(line: &str) -> Result<Option<Command>, CommandError> {
+        let line = line.split(COMMENT).next().unwrap_or_default().trim();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let command = match words[..] {
+            [] => return Ok(None),
+            [word] if word.eq_ignore_ascii_case("d") => Command::Draw,
+            [from, to] => Command::Transfer {
+                from: Self::pile(from)?,
+                to: Self::target(to)?,
+                count: 1,
+            },
+            [from, to, count] => Command::Transfer {
+                from: Self::pile(from)?,
+                to: Self::target(to)?,
+                count: match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(CommandError::InvalidCount(count.to_string())),
+                },
+            },
+            _ => return Err(CommandError::UnknownCommand(line.to_string())),
+        };
+        Ok(Some(command))
+    }
+
+    /// Returns the pile with the given name
+    fn pile(name: &str) -> Result<PileIdentifier, CommandError> {
+        notation::pileByName(name).ok_or_else(|| CommandError::UnknownPile(name.to_string()))
+    }
+
+    /// Returns the destination with the given name
+    fn target(name: &str) -> Result<Target, CommandError> {
+        if name.eq_ignore_ascii_case("f") {
+            Ok(Target::Foundation)
+        } else {
+            Self::pile(name).map(Target::Pile)
+        }
+    }
+
+    /// Returns the command that makes a move, or None for turning over a card, which the driver does on its own
+    pub fn fromMove(action: &Move) -> Option<Command> {
+        match action {
+            Move::DrawFromDeck | Move::RecycleDiscard => Some(Command::Draw),
+            Move::Flip(_) => None,
+            _ => Some(Command::Transfer {
+                from: action.source()?,
+                to: Target::Pile(action.destination()?),
+                count: action.count(),
+            }),
+        }
+    }
+
+    /// Returns the move the command stands for in the given position and checks that it is legal
+    pub fn toMove(&self, engine: &Engine) -> Result<Move, CommandError> {
+        let action = match *self {
+            Command::Draw if engine.identifyPile(Deck).empty() => Move::RecycleDiscard,
+            Command::Draw => Move::DrawFromDeck,
+            Command::Transfer { from, to, count } => {
+                let to = match to {
+                    Target::Pile(to) => to,
+                    Target::Foundation => {
+                        let card = engine
+                            .identifyPile(from)
+                            .getTopCard()
+                            .ok_or(CommandError::Illegal(MoveError::NotEnoughCards(from)))?;
+                        engine.foundationFor(card).ok_or(CommandError::NoFoundation(from))?
+                    }
+                };
+                Move::transfer(from, to, count).ok_or_else(|| CommandError::NotAMove(self.to_string()))?
+            }
+        };
+        engine.validate(&action).map_err(CommandError::Illegal)?;
+        Ok(action)
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Draw => write!(f, "d"),
+            Command::Transfer { from, to, count } => {
+                let to = match to {
+                    Target::Pile(to) => notation::pileName(*to),
+                    Target::Foundation => "f",
+                };
+                write!(f, "{} {}", notation::pileName(*from), to)?;
+                if *count != 1 {
+                    write!(f, " {}", count)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::difficulty::Difficulty;
+
+    fn transfer(from: PileIdentifier, to: Target, count: usize) -> Option<Command> {
+        Some(Command::Transfer { from, to, count })
+    }
+
+    #[test]
+    fn parsesCommands() {
+        assert_eq!(Command::parse("d"), Ok(Some(Command::Draw)));
+        assert_eq!(Command::parse("  D  "), Ok(Some(Command::Draw)));
+        assert_eq!(Command::parse("t3 f"), Ok(transfer(Pile3, Target::Foundation, 1)));
+        assert_eq!(Command::parse("W T5"), Ok(transfer(Discard, Target::Pile(Pile5), 1)));
+        assert_eq!(Command::parse("t2 t6 3 # the run"), Ok(transfer(Pile2, Target::Pile(Pile6), 3)));
+        assert_eq!(Command::parse("f1 t4"), Ok(transfer(Final1, Target::Pile(Pile4), 1)));
+    }
+
+    #[test]
+    fn skipsEmptyLinesAndComments() {
+        assert_eq!(Command::parse(""), Ok(None));
+        assert_eq!(Command::parse("   "), Ok(None));
+        assert_eq!(Command::parse("# d"), Ok(None));
+    }
+
+    #[test]
+    fn rejectsInvalidLines() {
+        assert_eq!(Command::parse("t1"), Err(CommandError::UnknownCommand("t1".to_string())));
+        assert_eq!(Command::parse("t1 t2 3 4"), Err(CommandError::UnknownCommand("t1 t2 3 4".to_string())));
+        assert_eq!(Command::parse("t8 t2"), Err(CommandError::UnknownPile("t8".to_string())));
+        assert_eq!(Command::parse("t1 x"), Err(CommandError::UnknownPile("x".to_string())));
+        assert_eq!(Command::parse("t1 t2 0"), Err(CommandError::InvalidCount("0".to_string())));
+        assert_eq!(Command::parse("t1 t2 two"), Err(CommandError::InvalidCount("two".to_string())));
+    }
+
+    #[test]
+    fn turnsCommandsIntoLegalMoves() {
+        // Deal 42 has TH on t1 and 9H on t2
+        let engine = Engine::withSeed(Difficulty::Easy, 42);
+        assert_eq!(Command::Draw.toMove(&engine), Ok(Move::DrawFromDeck));
+        let toDeck = Command::parse("t1 s").unwrap().unwrap();
+        assert_eq!(toDeck.toMove(&engine), Err(CommandError::NotAMove("t1 s".to_string())));
+        let toFinal = Command::parse("t1 f").unwrap().unwrap();
+        assert_eq!(toFinal.toMove(&engine), Err(CommandError::NoFoundation(Pile1)));
+        let onto = Command::parse("t1 t2").unwrap().unwrap();
+        assert!(matches!(onto.toMove(&engine), Err(CommandError::Illegal(_))));
+    }
+
+    #[test]
+    fn writesMovesAsCommands() {
+        let action = Move::TableauToTableau { from: Pile2, to: Pile6, count: 3 };
+        assert_eq!(Command::fromMove(&action).unwrap().to_string(), "t2 t6 3");
+        assert_eq!(Command::fromMove(&Move::RecycleDiscard), Some(Command::Draw));
+        assert_eq!(Command::fromMove(&Move::Flip(Pile4)), None);
+    }
+}
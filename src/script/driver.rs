@@ -0,0 +1,171 @@
+#![allow(non_snake_case)]
+
+use crate::card::pile::pile::PILE_IDENTIFIERS;
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use crate::engine::notation;
+use crate::script::command::{Command, CommandError};
+use std::io::{self, BufRead, Write};
+
+/// The exit status of the driver for a command line or a script that cannot be read
+pub const USAGE_ERROR: i32 = 2;
+
+#[derive(Clone, PartialEq, Debug)]
+/// How a script ended
+pub enum Outcome {
+    /// The script won the game
+    Won,
+    /// The script ran out of commands before the game was won
+    NotWon,
+    /// A line (counted from 1) could not be carried out
+    Failed {
+        line: usize,
+        text: String,
+        error: CommandError,
+    },
+}
+
+impl Outcome {
+    /// Returns the exit status of the driver: 0 if the game was won, 1 if it was not and 3 for a bad command
+    pub fn exitCode(&self) -> i32 {
+        match self {
+            Outcome::Won => 0,
+            Outcome::NotWon => 1,
+            Outcome::Failed { .. } => 3,
+        }
+    }
+}
+
+/// Plays the commands of a script on a game and writes the position after each of them
+pub struct Driver {
+    /// The game the commands are played on
+    engine: Engine,
+    /// Write only the last position
+    quiet: bool,
+}
+
+impl Driver {
+    /// Creates a driver for the given game
+    pub fn new(engine: Engine, quiet: bool) -> Driver {
+        Driver { engine, quiet }
+    }
+
+    /// Returns the game
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Plays the commands until the game is won, a command fails or the input ends.
+    /// The position is written before the first command and after every command, or once at the end if quiet.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> io::Result<Outcome> {
+        if !self.quiet {
+            writeln!(output, "{}", notation::format(&self.engine))?;
+        }
+        let mut outcome = Outcome::NotWon;
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            let played = match Command::parse(&line) {
+                Ok(None) => continue,
+                Ok(Some(command)) => self.play(&command),
+                Err(error) => Err(error),
+            };
+            if let Err(error) = played {
+                outcome = Outcome::Failed {
+                    line: index + 1,
+                    text: line.trim().to_string(),
+                    error,
+                };
+                break;
+            }
+            if !self.quiet {
+                writeln!(output, "{}", notation::format(&self.engine))?;
+            }
+            if self.engine.isGameOver() {
+                outcome = Outcome::Won;
+                break;
+            }
+        }
+        if self.quiet {
+            writeln!(output, "{}", notation::format(&self.engine))?;
+        }
+        Ok(outcome)
+    }
+
+    /// Plays a command and turns over the face-down card it uncovers
+    fn play(&mut self, command: &Command) -> Result<(), CommandError> {
+        let action = command.toMove(&self.engine)?;
+        self.engine.apply(action).map_err(CommandError::Illegal)?;
+        for identifier in PILE_IDENTIFIERS {
+            if self.engine.identifyPile(identifier).getTopCard().is_some_and(|card| !card.isFaceUp) {
+                self.engine.apply(Move::Flip(identifier)).map_err(CommandError::Illegal)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::difficulty::Difficulty;
+    use crate::solver::result::SolverResult;
+    use crate::solver::solver::Solver;
+
+    /// Returns the script of the moves that win deal 0, found by the solver (the driver turns the cards over itself)
+    fn winningScript() -> String {
+        let SolverResult::Won(moves) = Solver::default().solve(&Engine::withSeed(Difficulty::Easy, 0)) else {
+            panic!("deal 0 can be won");
+        };
+        moves.iter().filter_map(Command::fromMove).map(|command| format!("{}\n", command)).collect()
+    }
+
+    fn run(seed: u64, script: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let engine = Engine::withSeed(Difficulty::Easy, seed);
+        let outcome = Driver::new(engine, false).run(script.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn endsWithZeroWhenTheGameIsWon() {
+        let script = winningScript();
+        let (outcome, output) = run(0, &format!("# deal 0\n\n{}d\n", script));
+        assert_eq!(outcome, Outcome::Won);
+        assert_eq!(outcome.exitCode(), 0);
+        // The position before the first command and after every command up to the winning one
+        assert_eq!(output.lines().count(), script.lines().count() + 1);
+    }
+
+    #[test]
+    fn endsWithOneWhenTheCommandsRunOut() {
+        let (outcome, output) = run(42, "d\n# nothing else\n");
+        assert_eq!(outcome, Outcome::NotWon);
+        assert_eq!(outcome.exitCode(), 1);
+        assert_eq!(output.lines().count(), 2);
+    }
+
+    #[test]
+    fn endsWithThreeAtAnInvalidCommand() {
+        // Deal 42 has TH on t1 and 9H on t2
+        let (outcome, output) = run(42, "d\nt1 t2\nd\n");
+        let Outcome::Failed { line, text, error } = &outcome else {
+            panic!("the script did not fail: {:?}", outcome);
+        };
+        assert_eq!((*line, text.as_str()), (2, "t1 t2"));
+        assert!(matches!(error, CommandError::Illegal(_)));
+        assert_eq!(outcome.exitCode(), 3);
+        assert_eq!(output.lines().count(), 2);
+
+        let (outcome, _) = run(42, "t9 f\n");
+        assert_eq!(outcome.exitCode(), 3);
+    }
+
+    #[test]
+    fn writesOnlyTheLastPositionWhenQuiet() {
+        let mut driver = Driver::new(Engine::withSeed(Difficulty::Easy, 42), true);
+        let mut output = Vec::new();
+        assert_eq!(driver.run("d\nd\n".as_bytes(), &mut output).unwrap(), Outcome::NotWon);
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, format!("{}\n", notation::format(driver.engine())));
+    }
+}
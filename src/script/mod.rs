@@ -0,0 +1,3 @@
+pub mod command;
+pub mod driver;
+pub mod options;
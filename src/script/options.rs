@@ -0,0 +1,109 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::game::arguments::Arguments;
+use crate::game::difficulty::Difficulty;
+use crate::game::options::OptionsError;
+use std::path::PathBuf;
+
+/// The usage text printed when the command line cannot be parsed
+pub const USAGE: &str = "Usage: solitaire-cli [--deal <number>] [--draw 1|3] [--position <text>] [--quiet] [--solve] [<script>]";
+
+#[derive(Clone, Debug)]
+/// The options given on the command line of the script driver
+pub struct Options {
+    /// The deal number (seed) to play instead of a random one
+    pub deal: Option<u64>,
+    /// How many cards are drawn from the deck at once
+    pub difficulty: Difficulty,
+    /// The position to play from instead of a deal
+    pub position: Option<Engine>,
+    /// Print only the last position instead of the position after every command
+    pub quiet: bool,
+    /// Print the commands that win the game instead of playing a script
+    pub solve: bool,
+    /// The file the commands are read from instead of the standard input
+    pub script: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            deal: None,
+            difficulty: Difficulty::Easy,
+            position: None,
+            quiet: false,
+            solve: false,
+            script: None,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the given arguments (without the program name)
+    pub fn parse<I: IntoIterator<Item = String>>(arguments: I) -> Result<Options, OptionsError> {
+        let mut options = Options::default();
+        let mut arguments = Arguments::new(arguments);
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--deal" => options.deal = Some(arguments.deal(&argument)?),
+                "--draw" => options.difficulty = arguments.draw(&argument)?,
+                "--position" => options.position = Some(arguments.position(&argument)?),
+                "--quiet" => options.quiet = true,
+                "--solve" => options.solve = true,
+                _ if !argument.starts_with("--") && options.script.is_none() => {
+                    options.script = Some(PathBuf::from(argument));
+                }
+                _ => return Err(OptionsError::UnknownOption(argument)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::notation;
+
+    fn parse(arguments: &str) -> Result<Options, OptionsError> {
+        Options::parse(arguments.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn readsTheOptions() {
+        let options = parse("--deal 42 --draw 3 --quiet moves.txt").unwrap();
+        assert_eq!(options.deal, Some(42));
+        assert_eq!(options.difficulty, Difficulty::Hard);
+        assert!(options.quiet);
+        assert!(!options.solve);
+        assert_eq!(options.script, Some(PathBuf::from("moves.txt")));
+        assert!(parse("--solve").unwrap().solve);
+    }
+
+    #[test]
+    fn readsThePosition() {
+        let position = notation::format(&Engine::withSeed(Difficulty::Hard, 5));
+        let options = Options::parse(["--position".to_string(), position.clone()]).unwrap();
+        assert_eq!(options.position.map(|engine| notation::format(&engine)), Some(position));
+    }
+
+    // The driver exits with USAGE_ERROR for each of these
+    #[test]
+    fn rejectsInvalidCommandLines() {
+        assert_eq!(parse("--deal").unwrap_err(), OptionsError::MissingValue("--deal".to_string()));
+        assert_eq!(
+            parse("--deal -1").unwrap_err(),
+            OptionsError::InvalidValue("--deal".to_string(), "-1".to_string())
+        );
+        assert_eq!(
+            parse("--draw 2").unwrap_err(),
+            OptionsError::InvalidValue("--draw".to_string(), "2".to_string())
+        );
+        assert!(matches!(parse("--position draw1,t1:KS").unwrap_err(), OptionsError::InvalidPosition(_)));
+        assert_eq!(parse("--fast").unwrap_err(), OptionsError::UnknownOption("--fast".to_string()));
+        assert_eq!(parse("one.txt two.txt").unwrap_err(), OptionsError::UnknownOption("two.txt".to_string()));
+    }
+}
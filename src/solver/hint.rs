@@ -0,0 +1,57 @@
+#![allow(non_snake_case)]
+
+use crate::card::pile::pile::FINAL_IDENTIFIERS;
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use std::cmp::Reverse;
+
+/// Rates how useful a move is, or returns None if it is legal but does not help
+fn usefulness(engine: &Engine, action: &Move) -> Option<u32> {
+    match action {
+        Move::Flip(_) => Some(100),
+        Move::TableauToFoundation { .. } => Some(90),
+        Move::DiscardToFoundation { .. } => Some(85),
+        Move::TableauToTableau { from, to, count } => {
+            let pile = engine.identifyPile(*from);
+            let index = pile.size() - count;
+            if index == 0 {
+                // Emptying a pile only helps if the cards do not just move to another empty pile
+                if engine.identifyPile(*to).empty() {
+                    return None;
+                }
+                return Some(60);
+            }
+            let below = &pile.cards[index - 1];
+            if !below.isFaceUp {
+                let faceDown = pile.cards.iter().filter(|card| !card.isFaceUp).count() as u32;
+                Some(70 + faceDown)
+            } else if FINAL_IDENTIFIERS
+                .into_iter()
+                .any(|identifier| engine.canBeStacked(below, identifier))
+            {
+                // Splitting a run is only useful if it frees a card for a final pile
+                Some(35)
+            } else {
+                None
+            }
+        }
+        Move::DiscardToTableau { .. } => Some(50),
+        Move::FoundationToTableau { .. } | Move::DrawFromDeck | Move::RecycleDiscard => None,
+    }
+}
+
+/// Returns the useful moves of the position, the best one first. Deck moves are not included.
+pub fn hints(engine: &Engine) -> Vec<Move> {
+    let mut rated: Vec<(u32, Move)> = engine
+        .legalMoves()
+        .into_iter()
+        .filter_map(|action| usefulness(engine, &action).map(|rating| (rating, action)))
+        .collect();
+    rated.sort_by_key(|(rating, _)| Reverse(*rating));
+    rated.into_iter().map(|(_, action)| action).collect()
+}
+
+/// Checks if a card can be drawn from the deck, or the discard pile can be returned to it
+pub fn canDraw(engine: &Engine) -> bool {
+    engine.validate(&Move::DrawFromDeck).is_ok() || engine.validate(&Move::RecycleDiscard).is_ok()
+}
@@ -0,0 +1,4 @@
+pub mod hint;
+pub mod position;
+pub mod result;
+pub mod solver;
@@ -0,0 +1,320 @@
+#![allow(non_snake_case)]
+
+use crate::card::card::Card;
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::pile::pile::{FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use crate::game::difficulty::Difficulty;
+
+/// A card packed into a byte: suit index * 13 + rank index
+type Code = u8;
+/// Ends a pile in the key of a position (it is not the code of a card)
+const END_OF_PILE: u8 = u8::MAX;
+
+/// Packs a card into a byte
+fn encode(card: &Card) -> Code {
+    card.key() as Code
+}
+
+/// Returns the suit index of a packed card
+fn suit(code: Code) -> u8 {
+    code / 13
+}
+
+/// Returns the rank index of a packed card (Ace is 0, King is 12)
+fn rank(code: Code) -> u8 {
+    code % 13
+}
+
+/// Checks if a packed card is red
+fn isRed(code: Code) -> bool {
+    suit(code) < 2
+}
+
+#[derive(Clone, Debug)]
+/// A compact copy of the engine's piles that is cheap to clone and hash
+pub struct Position {
+    /// The cards of pile1 to pile7, bottom card first
+    tableau: [Vec<Code>; 7],
+    /// The number of face-down cards at the bottom of each pile
+    faceDown: [usize; 7],
+    /// The deck, top card last
+    deck: Vec<Code>,
+    /// The discard pile, top card last
+    discard: Vec<Code>,
+    /// The top card of final1 to final4
+    finals: [Option<Code>; 4],
+    /// The number of cards drawn from the deck at once
+    drawCount: usize,
+    /// How many more times the discard pile may be turned over (None if there is no limit)
+    recyclesLeft: Option<u32>,
+}
+
+impl Position {
+    /// Copies the position of the engine
+    pub fn fromEngine(engine: &Engine) -> Position {
+        let codes = |identifier: PileIdentifier| -> Vec<Code> {
+            engine.identifyPile(identifier).cards.iter().map(encode).collect()
+        };
+        let mut tableau: [Vec<Code>; 7] = Default::default();
+        let mut faceDown = [0; 7];
+        for (index, identifier) in PILE_IDENTIFIERS.into_iter().enumerate() {
+            let pile = engine.identifyPile(identifier);
+            faceDown[index] = pile.cards.iter().take_while(|card| !card.isFaceUp).count();
+            tableau[index] = codes(identifier);
+        }
+        let mut finals = [None; 4];
+        for (index, identifier) in FINAL_IDENTIFIERS.into_iter().enumerate() {
+            finals[index] = engine.identifyPile(identifier).getTopCard().map(encode);
+        }
+
+        Position {
+            tableau,
+            faceDown,
+            deck: codes(Deck),
+            discard: codes(Discard),
+            finals,
+            drawCount: if engine.difficulty() == Difficulty::Easy { 1 } else { 3 },
+            recyclesLeft: engine.recyclesLeft(),
+        }
+    }
+
+    /// Checks if every card is on the final piles
+    pub fn isWon(&self) -> bool {
+        self.finals.iter().all(|top| top.map(rank) == Some(12))
+    }
+
+    /// Returns the number of cards on the final piles
+    pub fn foundationCards(&self) -> usize {
+        self.finals.iter().map(|top| top.map_or(0, |code| rank(code) as usize + 1)).sum()
+    }
+
+    /// Returns the position written out in full, so that two positions have the same key only if they
+    /// have the same outcome. It is equal for positions which only differ in the order of pile1 to pile7
+    /// or of the final piles: the piles are sorted and the final piles are given by the height of each suit.
+    pub fn key(&self) -> Vec<u8> {
+        let mut piles: Vec<(usize, &Vec<Code>)> =
+            self.faceDown.iter().copied().zip(self.tableau.iter()).collect();
+        piles.sort();
+        let mut heights = [0u8; 4];
+        for code in self.finals.iter().flatten() {
+            heights[suit(*code) as usize] = rank(*code) + 1;
+        }
+
+        let mut key = Vec::with_capacity(80);
+        for (faceDown, cards) in piles {
+            key.push(faceDown as u8);
+            key.extend_from_slice(cards);
+            key.push(END_OF_PILE);
+        }
+        key.extend_from_slice(&heights);
+        key.extend_from_slice(&self.deck);
+        key.push(END_OF_PILE);
+        key.extend_from_slice(&self.discard);
+        key.push(END_OF_PILE);
+        match self.recyclesLeft {
+            Some(left) => key.extend_from_slice(&left.to_le_bytes()),
+            None => key.push(END_OF_PILE),
+        }
+        key
+    }
+
+    /// Returns the index of the final pile a card can be moved to
+    fn finalFor(&self, code: Code) -> Option<usize> {
+        if rank(code) == 0 {
+            return self.finals.iter().position(|top| top.is_none());
+        }
+        self.finals.iter().position(|top| *top == Some(code - 1))
+    }
+
+    /// Checks if a card can be placed on a pile
+    fn fitsOnPile(&self, code: Code, pile: usize) -> bool {
+        match self.tableau[pile].last() {
+            None => rank(code) == 12,
+            Some(&top) => isRed(top) != isRed(code) && rank(top) == rank(code) + 1,
+        }
+    }
+
+    /// Checks if a card can go to its final pile without ever being needed on a pile again:
+    /// both cards of the other color it could hold are already on the final piles
+    fn isSafeForFinal(&self, code: Code) -> bool {
+        if rank(code) <= 1 {
+            return true;
+        }
+        let mut heights = [0u8; 4];
+        for top in self.finals.iter().flatten() {
+            heights[suit(*top) as usize] = rank(*top) + 1;
+        }
+        let others = if isRed(code) { [2, 3] } else { [0, 1] };
+        others.iter().all(|&other| heights[other] >= rank(code))
+    }
+
+    /// Returns the moves worth searching, best first.
+    /// Flips and safe moves to the final piles are forced, since they never hurt.
+    /// Unless `complete` is set, moves that rarely help are left out: splitting a run on a pile
+    /// (unless it frees a card for a final pile) and moving cards back from the final piles.
+    pub fn candidateMoves(&self, complete: bool) -> Vec<Move> {
+        for (pile, identifier) in PILE_IDENTIFIERS.iter().enumerate() {
+            if !self.tableau[pile].is_empty() && self.faceDown[pile] == self.tableau[pile].len() {
+                return vec![Move::Flip(*identifier)];
+            }
+        }
+
+        let mut toFinal = Vec::new();
+        for (pile, identifier) in PILE_IDENTIFIERS.iter().enumerate() {
+            if let Some(&code) = self.tableau[pile].last() {
+                if let Some(index) = self.finalFor(code) {
+                    let action = Move::TableauToFoundation {
+                        from: *identifier,
+                        to: FINAL_IDENTIFIERS[index],
+                    };
+                    if self.isSafeForFinal(code) {
+                        return vec![action];
+                    }
+                    toFinal.push(action);
+                }
+            }
+        }
+        if let Some(&code) = self.discard.last() {
+            if let Some(index) = self.finalFor(code) {
+                let action = Move::DiscardToFoundation { to: FINAL_IDENTIFIERS[index] };
+                if self.isSafeForFinal(code) {
+                    return vec![action];
+                }
+                toFinal.push(action);
+            }
+        }
+
+        let mut revealing = Vec::new();
+        let mut other = Vec::new();
+        for (from, source) in PILE_IDENTIFIERS.iter().enumerate() {
+            let pile = &self.tableau[from];
+            for index in self.faceDown[from]..pile.len() {
+                // Moving a whole pile to an empty pile changes nothing
+                let targets = self.pileTargets(pile[index], index == 0);
+                for to in targets.into_iter().filter(|&to| to != from) {
+                    let action = Move::TableauToTableau {
+                        from: *source,
+                        to: PILE_IDENTIFIERS[to],
+                        count: pile.len() - index,
+                    };
+                    if index == self.faceDown[from] {
+                        revealing.push(action);
+                    } else if complete || self.finalFor(pile[index - 1]).is_some() {
+                        other.push(action);
+                    }
+                }
+            }
+        }
+
+        let mut fromDiscard = Vec::new();
+        if let Some(&code) = self.discard.last() {
+            for to in self.pileTargets(code, false) {
+                fromDiscard.push(Move::DiscardToTableau { to: PILE_IDENTIFIERS[to] });
+            }
+        }
+
+        let mut fromFinal = Vec::new();
+        for (index, top) in self.finals.iter().enumerate().filter(|_| complete) {
+            if let Some(code) = *top {
+                for to in self.pileTargets(code, false) {
+                    fromFinal.push(Move::FoundationToTableau {
+                        from: FINAL_IDENTIFIERS[index],
+                        to: PILE_IDENTIFIERS[to],
+                    });
+                }
+            }
+        }
+
+        let mut moves = toFinal;
+        moves.append(&mut revealing);
+        moves.append(&mut fromDiscard);
+        if !self.deck.is_empty() {
+            moves.push(Move::DrawFromDeck);
+        } else if !self.discard.is_empty() && self.recyclesLeft != Some(0) {
+            moves.push(Move::RecycleDiscard);
+        }
+        moves.append(&mut other);
+        moves.append(&mut fromFinal);
+        moves
+    }
+
+    /// Returns the indexes of the piles a card can be placed on.
+    /// Only the first empty pile is returned, since all empty piles are equivalent.
+    fn pileTargets(&self, code: Code, skipEmpty: bool) -> Vec<usize> {
+        let mut targets = Vec::new();
+        let mut emptyFound = skipEmpty;
+        for (to, pile) in self.tableau.iter().enumerate() {
+            if pile.is_empty() {
+                if !emptyFound && rank(code) == 12 {
+                    targets.push(to);
+                }
+                emptyFound = true;
+            } else if self.fitsOnPile(code, to) {
+                targets.push(to);
+            }
+        }
+        targets
+    }
+
+    /// Returns the index of pile1 to pile7
+    fn pileIndex(identifier: &PileIdentifier) -> usize {
+        PILE_IDENTIFIERS.iter().position(|pile| pile == identifier).unwrap()
+    }
+
+    /// Returns the index of final1 to final4
+    fn finalIndex(identifier: &PileIdentifier) -> usize {
+        FINAL_IDENTIFIERS.iter().position(|pile| pile == identifier).unwrap()
+    }
+
+    /// Removes the top card of a final pile
+    fn popFinal(&mut self, index: usize) -> Code {
+        let code = self.finals[index].unwrap();
+        self.finals[index] = if rank(code) == 0 { None } else { Some(code - 1) };
+        code
+    }
+
+    /// Applies a move produced by candidateMoves
+    pub fn apply(&mut self, action: &Move) {
+        match action {
+            Move::DrawFromDeck => {
+                for _ in 0..self.drawCount.min(self.deck.len()) {
+                    let code = self.deck.pop().unwrap();
+                    self.discard.push(code);
+                }
+            }
+            Move::RecycleDiscard => {
+                self.deck = self.discard.drain(..).rev().collect();
+                self.recyclesLeft = self.recyclesLeft.map(|left| left - 1);
+            }
+            Move::Flip(identifier) => {
+                self.faceDown[Self::pileIndex(identifier)] -= 1;
+            }
+            Move::TableauToTableau { from, to, count } => {
+                let (from, to) = (Self::pileIndex(from), Self::pileIndex(to));
+                let split = self.tableau[from].len() - count;
+                let cards = self.tableau[from].split_off(split);
+                self.tableau[to].extend(cards);
+            }
+            Move::TableauToFoundation { from, to } => {
+                let code = self.tableau[Self::pileIndex(from)].pop().unwrap();
+                self.finals[Self::finalIndex(to)] = Some(code);
+            }
+            Move::DiscardToTableau { to } => {
+                let code = self.discard.pop().unwrap();
+                self.tableau[Self::pileIndex(to)].push(code);
+            }
+            Move::DiscardToFoundation { to } => {
+                let code = self.discard.pop().unwrap();
+                self.finals[Self::finalIndex(to)] = Some(code);
+            }
+            Move::FoundationToTableau { from, to } => {
+                let code = self.popFinal(Self::finalIndex(from));
+                self.tableau[Self::pileIndex(to)].push(code);
+            }
+        }
+    }
+}
+
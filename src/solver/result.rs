@@ -0,0 +1,12 @@
+use crate::engine::moves::Move;
+
+#[derive(Clone, PartialEq, Debug)]
+/// The verdict of the solver
+pub enum SolverResult {
+    /// The game can be won by applying these moves in order
+    Won(Vec<Move>),
+    /// Every reachable position was searched and none of them is won
+    Unwinnable,
+    /// The node or time limit was reached before a verdict
+    Unknown,
+}
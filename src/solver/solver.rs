@@ -0,0 +1,192 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use crate::solver::position::Position;
+use crate::solver::result::SolverResult;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// How often (in nodes) the time limit is checked
+const TIME_CHECK_INTERVAL: usize = 1024;
+
+/// A position on the search path together with the moves that are left to try from it
+struct Frame {
+    position: Position,
+    moves: Vec<Move>,
+    next: usize,
+}
+
+#[derive(Clone, Debug)]
+/// A depth-first Klondike solver. It knows the face-down cards, so it answers whether a deal
+/// can be won at all, not whether a player could find the win.
+pub struct Solver {
+    /// The maximum number of positions to visit
+    maxNodes: usize,
+    /// The maximum time to search for
+    maxTime: Duration,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new(2_000_000, Duration::from_secs(10))
+    }
+}
+
+impl Solver {
+    /// Creates a solver with the given node and time limits
+    pub fn new(maxNodes: usize, maxTime: Duration) -> Solver {
+        Solver { maxNodes, maxTime }
+    }
+
+    /// Searches for a sequence of moves that wins the game of the engine, using its difficulty.
+    /// A quick search that skips rarely useful moves runs first with half of the node limit;
+    /// only a search over every move can prove that the game is unwinnable.
+    pub fn solve(&self, engine: &Engine) -> SolverResult {
+        if engine.isGameOver() {
+            return SolverResult::Won(Vec::new());
+        }
+
+        let start = Instant::now();
+        let root = Position::fromEngine(engine);
+        let mut nodes: usize = 0;
+        match self.search(&root, false, self.maxNodes / 2, start, &mut nodes) {
+            SolverResult::Won(moves) => SolverResult::Won(moves),
+            _ => self.search(&root, true, self.maxNodes, start, &mut nodes),
+        }
+    }
+
+    /// A depth-first search from the root position that stops after `maxNodes` nodes in total
+    fn search(
+        &self,
+        root: &Position,
+        complete: bool,
+        maxNodes: usize,
+        start: Instant,
+        nodes: &mut usize,
+    ) -> SolverResult {
+        // The transposition table: positions that were already searched or are on the current path
+        let mut visited: HashSet<Vec<u8>> = HashSet::new();
+        visited.insert(root.key());
+        let mut stack = vec![Frame {
+            moves: root.candidateMoves(complete),
+            position: root.clone(),
+            next: 0,
+        }];
+        let mut path: Vec<Move> = Vec::new();
+
+        while let Some(frame) = stack.last_mut() {
+            if frame.next == frame.moves.len() {
+                stack.pop();
+                path.pop();
+                continue;
+            }
+            let action = frame.moves[frame.next].clone();
+            frame.next += 1;
+
+            let mut position = frame.position.clone();
+            position.apply(&action);
+            if !visited.insert(position.key()) {
+                continue;
+            }
+            path.push(action);
+            if position.isWon() {
+                return SolverResult::Won(path);
+            }
+
+            *nodes += 1;
+            if *nodes >= maxNodes
+                || (nodes.is_multiple_of(TIME_CHECK_INTERVAL) && start.elapsed() >= self.maxTime)
+            {
+                return SolverResult::Unknown;
+            }
+            stack.push(Frame {
+                moves: position.candidateMoves(complete),
+                position,
+                next: 0,
+            });
+        }
+
+        SolverResult::Unwinnable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::pile::identifier::PileIdentifier::*;
+    use crate::engine::notation;
+
+    /// Writes the cards of a suit from `first` to `last` (1 is the Ace), face down if asked
+    fn cards(suit: char, first: usize, last: usize, faceDown: bool) -> String {
+        let prefix = if faceDown { "#" } else { "" };
+        (first..=last)
+            .map(|rank| format!("{}{}{}", prefix, "A23456789TJQK".as_bytes()[rank - 1] as char, suit))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    fn solver() -> Solver {
+        Solver::new(10_000, Duration::from_secs(10))
+    }
+
+    #[test]
+    fn findsTheLastMove() {
+        let text = format!(
+            "draw1 f1:{} f2:{} f3:{} f4:{} t1:KS",
+            cards('H', 1, 13, false),
+            cards('D', 1, 13, false),
+            cards('C', 1, 13, false),
+            cards('S', 1, 12, false)
+        );
+        let engine = notation::parse(&text).unwrap();
+        let expected = vec![Move::TableauToFoundation { from: Pile1, to: Final4 }];
+        assert_eq!(solver().solve(&engine), SolverResult::Won(expected));
+    }
+
+    #[test]
+    fn provesAStuckGameUnwinnable() {
+        // Each two covers the Ace the other one needs and every other card is face down below them
+        let text = format!(
+            "draw1 t1:{},{},{},#AS,2H t2:{},#AH,2S",
+            cards('C', 1, 13, true),
+            cards('D', 1, 13, true),
+            cards('H', 3, 13, true),
+            cards('S', 3, 13, true)
+        );
+        let engine = notation::parse(&text).unwrap();
+        assert_eq!(solver().solve(&engine), SolverResult::Unwinnable);
+    }
+
+    #[test]
+    fn keepsACardOffTheFinalPilesWhileItIsNeeded() {
+        // The Five of Hearts must first hold the Four of Clubs and the Three of Diamonds, which
+        // cover the Two of Diamonds. Every other card is face down below it, in the order it goes up.
+        let mut hidden = Vec::new();
+        for rank in (6..=13).rev() {
+            hidden.extend(['D', 'S', 'C', 'H'].map(|suit| cards(suit, rank, rank, true)));
+        }
+        let text = format!(
+            "draw1 f1:{} f2:AD f3:{} f4:{} t1:5H t2:{},#5D,#5S,#5C,#4D,#4S,#2D,4C,3D",
+            cards('H', 1, 4, false),
+            cards('C', 1, 3, false),
+            cards('S', 1, 3, false),
+            hidden.join(",")
+        );
+        let engine = notation::parse(&text).unwrap();
+        assert!(matches!(solver().solve(&engine), SolverResult::Won(_)));
+    }
+
+    #[test]
+    fn winsADeal() {
+        let engine = Engine::withSeed(crate::game::difficulty::Difficulty::Easy, 0);
+        let SolverResult::Won(moves) = Solver::default().solve(&engine) else {
+            panic!("deal 0 can be won");
+        };
+        let mut engine = engine;
+        for action in moves {
+            engine.apply(action).unwrap();
+        }
+        assert!(engine.isGameOver());
+    }
+}
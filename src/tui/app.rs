@@ -0,0 +1,252 @@
+#![allow(non_snake_case)]
+
+use crate::card::pile::pile::PILE_IDENTIFIERS;
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use crate::engine::notation;
+use crate::game::bankroll::Bankroll;
+use crate::game::cursor::Cursor;
+use crate::game::difficulty::Difficulty;
+use crate::game::preferences::Preferences;
+use crate::game::stats::{self, PlayerStats};
+use crate::solver::hint;
+use crate::tui::options::Options;
+use crate::tui::render::{self, Glyphs, TOP_COLUMNS};
+use crate::tui::terminal::{Key, Terminal};
+use std::io;
+use std::time::Instant;
+
+/// How long to wait for a key before the clock is redrawn, in milliseconds
+const CLOCK_INTERVAL: i32 = 1000;
+/// The time between two moves of the auto-complete, in milliseconds
+const AUTO_COMPLETE_INTERVAL: i32 = 100;
+
+/// The terminal front end: shows the engine's piles and turns keys into moves
+pub struct App {
+    /// The game being played
+    engine: Engine,
+    /// The pile the keys act on and the cards that were picked up
+    cursor: Cursor,
+    /// The characters the cards are drawn with
+    glyphs: Glyphs,
+    /// The choices made on the settings screen of the windowed game
+    preferences: Preferences,
+    /// The line shown below the table
+    message: String,
+    /// The hints of the current position and the one that is shown
+    hints: Vec<Move>,
+    hintIndex: usize,
+    /// Indicates if the cards are being moved to the final piles one by one
+    autoCompleting: bool,
+    /// Indicates if the player stopped the auto-complete, which then does not start again on its own
+    autoCompleteStopped: bool,
+    /// Indicates if the game was won (and already recorded)
+    won: bool,
+    /// Indicates if the player asked to quit
+    quit: bool,
+}
+
+impl App {
+    /// Creates the front end with a first game that starts from the given position, or else is dealt according to the options
+    pub fn new(options: Options) -> App {
+        let preferences = Preferences::load();
+        let engine = match options.position {
+            Some(engine) => Self::prepare(&preferences, engine),
+            None => Self::deal(&preferences, options.difficulty, options.deal.unwrap_or_else(rand::random)),
+        };
+        App {
+            engine,
+            cursor: Cursor::new(),
+            glyphs: options.glyphs,
+            preferences,
+            message: String::new(),
+            hints: Vec::new(),
+            hintIndex: 0,
+            autoCompleting: false,
+            autoCompleteStopped: false,
+            won: false,
+            quit: false,
+        }
+    }
+
+    /// Deals a game with the preferences of the windowed game
+    fn deal(preferences: &Preferences, difficulty: Difficulty, seed: u64) -> Engine {
+        Self::prepare(preferences, Engine::withScoring(difficulty, preferences.scoring, seed))
+    }
+
+    /// Applies the preferences of the windowed game to a game and puts its piles in their columns
+    fn prepare(preferences: &Preferences, mut engine: Engine) -> Engine {
+        engine.setUndoCountsAsMove(preferences.undoCountsAsMove);
+        // The cursor moves between the rows by the x coordinates of the piles, which are their columns here
+        for (identifier, column) in TOP_COLUMNS {
+            engine.placePile(identifier, column as f32, 0.0, 0.0, 0.0);
+        }
+        for (column, identifier) in PILE_IDENTIFIERS.into_iter().enumerate() {
+            engine.placePile(identifier, column as f32, 1.0, 0.0, 0.0);
+        }
+        engine
+    }
+
+    /// Plays until the player quits. A game that is not won counts as abandoned.
+    pub fn run(&mut self, terminal: &mut Terminal) -> io::Result<()> {
+        let mut last = Instant::now();
+        while !self.quit {
+            terminal.draw(&render::screen(&self.engine, &self.cursor, self.glyphs, &self.message))?;
+            let timeout = if self.autoCompleting { AUTO_COMPLETE_INTERVAL } else { CLOCK_INTERVAL };
+            let key = terminal.readKey(timeout)?;
+            let now = Instant::now();
+            self.engine.tick(now - last);
+            last = now;
+            match key {
+                Some(key) => self.key(key),
+                None => self.stepAutoComplete(),
+            }
+        }
+        self.abandon();
+        Ok(())
+    }
+
+    /// Carries out a key
+    fn key(&mut self, key: Key) {
+        if self.autoCompleting {
+            match key {
+                Key::Char('q' | 'Q') | Key::Ctrl('c') => self.quit = true,
+                Key::Escape | Key::Ctrl('z') => {
+                    self.autoCompleting = false;
+                    self.autoCompleteStopped = true;
+                }
+                _ => {}
+            }
+            return;
+        }
+        match key {
+            Key::Char('q' | 'Q') | Key::Ctrl('c') => self.quit = true,
+            Key::Char('n' | 'N') => self.newGame(),
+            // The position is shown so that it can be copied into a bug report or loaded with --position
+            Key::Char('p' | 'P') => self.message = notation::format(&self.engine),
+            _ if self.won => {}
+            Key::Escape => self.cursor.selected = None,
+            Key::Char('u' | 'U') | Key::Ctrl('z') => self.rewind(false),
+            Key::Char('r' | 'R') | Key::Ctrl('y') => self.rewind(true),
+            Key::Char('h' | 'H') => self.nextHint(),
+            Key::Char('a' | 'A') if self.engine.canAutoComplete() => {
+                self.cursor.selected = None;
+                self.autoCompleting = true;
+            }
+            Key::Char(digit @ '1'..='7') => {
+                let index = digit as usize - '1' as usize;
+                self.cursor.toPile(&self.engine, PILE_IDENTIFIERS[index]);
+            }
+            Key::Left => self.cursor.moveHorizontally(false),
+            Key::Right | Key::Tab => self.cursor.moveHorizontally(true),
+            Key::Up => self.cursor.moveUp(&self.engine),
+            Key::Down => self.cursor.moveDown(&self.engine),
+            Key::Char('d' | 'D') => self.cursor.toggleDeck(),
+            Key::Char('f' | 'F') => {
+                if let Some(action) = self.cursor.toFoundation(&self.engine) {
+                    self.apply(action);
+                }
+            }
+            Key::Enter | Key::Char(' ') => {
+                if let Some(action) = self.cursor.confirm(&self.engine) {
+                    self.apply(action);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies a move, or tells why it is not allowed
+    fn apply(&mut self, action: Move) {
+        match self.engine.apply(action) {
+            Ok(()) => {
+                self.message.clear();
+                self.hints.clear();
+                self.afterMove();
+            }
+            Err(error) => self.message = format!("Not allowed: {}", error),
+        }
+    }
+
+    /// Starts the auto-complete or ends the game once the position allows it
+    fn afterMove(&mut self) {
+        if self.engine.isGameOver() {
+            self.win();
+        } else if self.preferences.autoComplete && !self.autoCompleteStopped && self.engine.canAutoComplete() {
+            self.cursor.selected = None;
+            self.autoCompleting = true;
+        }
+    }
+
+    /// Moves the next card to its final pile while the auto-complete runs
+    fn stepAutoComplete(&mut self) {
+        if !self.autoCompleting {
+            return;
+        }
+        match self.engine.nextAutoCompleteMove() {
+            Some(action) => self.apply(action),
+            None => self.autoCompleting = false,
+        }
+    }
+
+    /// Takes back the last move, or makes it again (`redo`)
+    fn rewind(&mut self, redo: bool) {
+        self.cursor.selected = None;
+        self.hints.clear();
+        let rewound = if redo { self.engine.redo() } else { self.engine.undo() };
+        self.message = match (rewound, redo) {
+            (true, _) => String::new(),
+            (false, true) => "There is nothing to redo".to_string(),
+            (false, false) => "There is nothing to undo".to_string(),
+        };
+    }
+
+    /// Shows the best hint, or the next one if a hint is already shown
+    fn nextHint(&mut self) {
+        if self.hints.is_empty() {
+            self.hints = hint::hints(&self.engine);
+            self.hintIndex = 0;
+        } else {
+            self.hintIndex = (self.hintIndex + 1) % self.hints.len();
+        }
+        self.message = match self.hints.get(self.hintIndex) {
+            Some(action) => format!("Hint: {}", render::describe(action)),
+            None if hint::canDraw(&self.engine) => "Hint: Draw from the deck".to_string(),
+            None => "There are no moves left".to_string(),
+        };
+    }
+
+    /// Records the won game and tells how it went
+    fn win(&mut self) {
+        self.won = true;
+        self.autoCompleting = false;
+        PlayerStats::record(&self.engine, true);
+        Bankroll::settle(&self.engine);
+        self.message = format!(
+            "You won in {} with {} moves! Press N for a new game or Q to quit.",
+            stats::formatTime(self.engine.elapsed()),
+            self.engine.moves()
+        );
+    }
+
+    /// Records the current game as abandoned, unless it was won or not started
+    fn abandon(&mut self) {
+        if self.won || self.engine.moves() == 0 {
+            return;
+        }
+        PlayerStats::record(&self.engine, false);
+        Bankroll::settle(&self.engine);
+    }
+
+    /// Abandons the current game and deals a new one with the same number of cards drawn
+    fn newGame(&mut self) {
+        self.abandon();
+        self.engine = Self::deal(&self.preferences, self.engine.difficulty(), rand::random());
+        self.cursor = Cursor::new();
+        self.message.clear();
+        self.hints.clear();
+        self.autoCompleting = false;
+        self.autoCompleteStopped = false;
+        self.won = false;
+    }
+}
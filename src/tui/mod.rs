@@ -0,0 +1,4 @@
+pub mod app;
+pub mod options;
+pub mod render;
+pub mod terminal;
@@ -0,0 +1,54 @@
+#![allow(non_snake_case)]
+
+use crate::engine::engine::Engine;
+use crate::game::arguments::Arguments;
+use crate::game::difficulty::Difficulty;
+use crate::game::options::OptionsError;
+use crate::tui::render::Glyphs;
+
+/// The usage text printed when the command line cannot be parsed
+pub const USAGE: &str = "Usage: solitaire-tui [--deal <number>] [--draw 1|3] [--position <text>] [--ascii]";
+
+#[derive(Clone, Debug)]
+/// The options given on the command line of the terminal front end
+pub struct Options {
+    /// The deal number (seed) of the first game instead of a random one
+    pub deal: Option<u64>,
+    /// How many cards are drawn from the deck at once
+    pub difficulty: Difficulty,
+    /// The position the first game starts from instead of a deal
+    pub position: Option<Engine>,
+    /// The characters the cards are drawn with
+    pub glyphs: Glyphs,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            deal: None,
+            difficulty: Difficulty::Easy,
+            position: None,
+            glyphs: Glyphs::Unicode,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the given arguments (without the program name)
+    pub fn parse<I: IntoIterator<Item = String>>(arguments: I) -> Result<Options, OptionsError> {
+        let mut options = Options::default();
+        let mut arguments = Arguments::new(arguments);
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--deal" => options.deal = Some(arguments.deal(&argument)?),
+                "--draw" => options.difficulty = arguments.draw(&argument)?,
+                "--position" => options.position = Some(arguments.position(&argument)?),
+                "--ascii" => options.glyphs = Glyphs::Ascii,
+                _ => return Err(OptionsError::UnknownOption(argument)),
+            }
+        }
+
+        Ok(options)
+    }
+}
@@ -0,0 +1,257 @@
+#![allow(non_snake_case)]
+
+use crate::card::card::Card;
+use crate::card::color::Color;
+use crate::card::pile::identifier::PileIdentifier;
+use crate::card::pile::identifier::PileIdentifier::*;
+use crate::card::pile::pile::{FINAL_IDENTIFIERS, PILE_IDENTIFIERS};
+use crate::card::suit::Suit;
+use crate::engine::engine::Engine;
+use crate::engine::moves::Move;
+use crate::engine::scoring::Scoring;
+use crate::game::cursor::Cursor;
+use crate::game::difficulty::Difficulty;
+use crate::game::stats;
+
+/// The number of terminal columns between the left sides of two columns of piles
+const COLUMN_WIDTH: usize = 6;
+/// The number of terminal columns a card takes
+const CARD_WIDTH: usize = 3;
+/// The column of every pile of the top row (the discard pile fans out over the empty column next to it)
+pub const TOP_COLUMNS: [(PileIdentifier, usize); 6] =
+    [(Deck, 0), (Discard, 1), (Final1, 3), (Final2, 4), (Final3, 5), (Final4, 6)];
+/// The number of discard cards shown when three are drawn at a time
+const DISCARD_FAN: usize = 3;
+/// The keys, shown below the table
+const HELP: &str = "Arrows/1-7 move  D deck  F foundation  Enter pick up/drop  Esc cancel  \
+                    U undo  R redo  H hint  A auto-complete  P position  N new game  Q quit";
+
+/// The escape sequences that color the screen
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// The characters the cards are drawn with
+pub enum Glyphs {
+    /// Suit symbols and shaded card backs
+    Unicode,
+    /// H, D, C and S, for terminals without Unicode
+    Ascii,
+}
+
+impl Glyphs {
+    /// Returns the symbol of a suit
+    fn suit(self, suit: Suit) -> char {
+        match (self, suit) {
+            (Glyphs::Unicode, Suit::Heart) => '♥',
+            (Glyphs::Unicode, Suit::Diamond) => '♦',
+            (Glyphs::Unicode, Suit::Club) => '♣',
+            (Glyphs::Unicode, Suit::Spade) => '♠',
+            (Glyphs::Ascii, Suit::Heart) => 'H',
+            (Glyphs::Ascii, Suit::Diamond) => 'D',
+            (Glyphs::Ascii, Suit::Club) => 'C',
+            (Glyphs::Ascii, Suit::Spade) => 'S',
+        }
+    }
+
+    /// Returns the back of a face-down card
+    fn back(self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "░░░",
+            Glyphs::Ascii => "###",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+/// How a card is marked
+enum Highlight {
+    None,
+    /// Covered by the cursor
+    Cursor,
+    /// Picked up and waiting for a destination
+    Selected,
+}
+
+impl Highlight {
+    /// Returns the background color code of a card with this highlight
+    fn background(self) -> u8 {
+        match self {
+            Highlight::None => 47,
+            Highlight::Cursor => 46,
+            Highlight::Selected => 43,
+        }
+    }
+}
+
+/// One line of the screen, which keeps track of its width on the terminal
+#[derive(Default)]
+struct Line {
+    text: String,
+    width: usize,
+}
+
+impl Line {
+    /// Adds spaces up to the given column of piles
+    fn moveTo(&mut self, column: usize) {
+        let target = 1 + column * COLUMN_WIDTH;
+        while self.width < target {
+            self.text.push(' ');
+            self.width += 1;
+        }
+    }
+
+    /// Adds a card
+    fn push(&mut self, cell: String) {
+        self.text += &cell;
+        self.width += CARD_WIDTH;
+    }
+}
+
+/// Returns the screen: the status, the table, a message and the keys
+pub fn screen(engine: &Engine, cursor: &Cursor, glyphs: Glyphs, message: &str) -> String {
+    let mut lines = vec![format!("{}{}{}", BOLD, status(engine), RESET), String::new()];
+
+    let mut top = Line::default();
+    for (identifier, column) in TOP_COLUMNS {
+        top.moveTo(column);
+        let pile = engine.identifyPile(identifier);
+        let shown = match (identifier, engine.difficulty()) {
+            (Discard, Difficulty::Hard) => DISCARD_FAN,
+            _ => 1,
+        };
+        let cards = &pile.cards[pile.size().saturating_sub(shown)..];
+        if cards.is_empty() {
+            top.push(cell(glyphs, None, highlight(cursor, identifier, 0, 0)));
+        }
+        for (index, card) in cards.iter().enumerate() {
+            // Only the top card can be taken
+            let isTop = index + 1 == cards.len();
+            let mark = if isTop { highlight(cursor, identifier, 0, 1) } else { Highlight::None };
+            top.push(cell(glyphs, Some(card), mark));
+            top.text.push(' ');
+            top.width += 1;
+        }
+    }
+    lines.push(top.text);
+    lines.push(String::new());
+
+    let mut labels = Line::default();
+    for (column, _) in PILE_IDENTIFIERS.iter().enumerate() {
+        labels.moveTo(column);
+        labels.text += &format!("{} {} {}", DIM, column + 1, RESET);
+        labels.width += CARD_WIDTH;
+    }
+    lines.push(labels.text);
+
+    let height = PILE_IDENTIFIERS.iter().map(|identifier| engine.identifyPile(*identifier).size()).max().unwrap_or(0);
+    for row in 0..height.max(1) {
+        let mut line = Line::default();
+        for (column, identifier) in PILE_IDENTIFIERS.into_iter().enumerate() {
+            let pile = engine.identifyPile(identifier);
+            line.moveTo(column);
+            match pile.getCardByIndex(row) {
+                Some(card) => line.push(cell(glyphs, Some(card), highlight(cursor, identifier, row, pile.size()))),
+                None if row == 0 => line.push(cell(glyphs, None, highlight(cursor, identifier, 0, 0))),
+                None => {}
+            }
+        }
+        lines.push(line.text);
+    }
+
+    lines.push(String::new());
+    lines.push(message.to_string());
+    lines.push(format!("{}{}{}", DIM, HELP, RESET));
+    lines.join("\n")
+}
+
+/// Returns how the card at `index` of a pile of `size` cards is marked
+fn highlight(cursor: &Cursor, identifier: PileIdentifier, index: usize, size: usize) -> Highlight {
+    let covers = |count: usize| index + count.max(1) >= size.max(1);
+    if cursor.pile == identifier && covers(cursor.count) {
+        return Highlight::Cursor;
+    }
+    match cursor.selected {
+        Some((pile, count)) if pile == identifier && covers(count) => Highlight::Selected,
+        _ => Highlight::None,
+    }
+}
+
+/// Returns a card, a face-down card or an empty pile (None) in its colors
+fn cell(glyphs: Glyphs, card: Option<&Card>, highlight: Highlight) -> String {
+    let background = highlight.background();
+    match card {
+        Some(card) if card.isFaceUp => {
+            let foreground = match card.suit.getColor() {
+                Color::Red => 31,
+                Color::Black => 30,
+            };
+            let text = format!("{:>2}{}", card.rank.label(), glyphs.suit(card.suit));
+            format!("\x1b[{};{}m{}{}", foreground, background, text, RESET)
+        }
+        Some(_) => {
+            let background = if highlight == Highlight::None { 44 } else { background };
+            format!("\x1b[37;{}m{}{}", background, glyphs.back(), RESET)
+        }
+        None if highlight == Highlight::None => format!("{}[ ]{}", DIM, RESET),
+        None => format!("\x1b[30;{}m[ ]{}", background, RESET),
+    }
+}
+
+/// Returns the status line: the deal, the time, the moves, the passes through the deck and the score
+fn status(engine: &Engine) -> String {
+    let draw = match engine.difficulty() {
+        Difficulty::Easy => 1,
+        Difficulty::Hard => 3,
+    };
+    let pass = engine.recycles() + 1;
+    let passes = match engine.scoring().maxRecycles(engine.difficulty()) {
+        Some(max) => format!("{} of {}", pass, max + 1),
+        None => pass.to_string(),
+    };
+    let score = match engine.scoring() {
+        Scoring::Standard => engine.finalScore().to_string(),
+        Scoring::Vegas => format!("${}", engine.finalScore()),
+    };
+    let deal = engine.seed().map_or("Custom position".to_string(), |seed| format!("Deal #{}", seed));
+    format!(
+        "Solitaire  {}  Draw {}    Time: {}  Moves: {}  Pass: {}  Score: {}",
+        deal,
+        draw,
+        stats::formatTime(engine.elapsed()),
+        engine.moves(),
+        passes,
+        score
+    )
+}
+
+/// Returns the name of a pile as it is used in messages
+fn name(identifier: PileIdentifier) -> String {
+    if let Some(index) = PILE_IDENTIFIERS.iter().position(|pile| *pile == identifier) {
+        return format!("pile {}", index + 1);
+    }
+    if let Some(index) = FINAL_IDENTIFIERS.iter().position(|pile| *pile == identifier) {
+        return format!("final pile {}", index + 1);
+    }
+    match identifier {
+        Deck => "the deck".to_string(),
+        _ => "the discard pile".to_string(),
+    }
+}
+
+/// Describes a move in words, for the hints
+pub fn describe(action: &Move) -> String {
+    match action {
+        Move::DrawFromDeck => "Draw from the deck".to_string(),
+        Move::RecycleDiscard => "Turn the discard pile over".to_string(),
+        Move::Flip(pile) => format!("Turn over the top card of {}", name(*pile)),
+        _ => {
+            let (from, to) = (action.source().unwrap_or(Discard), action.destination().unwrap_or(Discard));
+            match action.count() {
+                1 => format!("Move the top card of {} to {}", name(from), name(to)),
+                count => format!("Move {} cards from {} to {}", count, name(from), name(to)),
+            }
+        }
+    }
+}
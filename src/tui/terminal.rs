@@ -0,0 +1,184 @@
+#![allow(non_snake_case)]
+
+use std::io::{self, Write};
+use std::mem::MaybeUninit;
+
+/// How long to wait for the rest of an escape sequence before taking Escape as a key, in milliseconds
+const ESCAPE_TIMEOUT: i32 = 30;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+/// A key pressed in the terminal
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    Tab,
+    /// A printable character
+    Char(char),
+    /// A letter pressed with Ctrl
+    Ctrl(char),
+}
+
+/// The terminal in raw mode on the alternate screen. It is restored when the Terminal is dropped.
+pub struct Terminal {
+    /// The settings of the terminal before it was switched to raw mode
+    original: libc::termios,
+}
+
+impl Terminal {
+    /// Switches the terminal to raw mode, shows the alternate screen and hides the cursor
+    pub fn open() -> io::Result<Terminal> {
+        if unsafe { libc::isatty(libc::STDIN_FILENO) } == 0 {
+            return Err(io::Error::other("the standard input is not a terminal"));
+        }
+        let mut original = MaybeUninit::<libc::termios>::uninit();
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let original = unsafe { original.assume_init() };
+        let mut raw = original;
+        unsafe { libc::cfmakeraw(&mut raw) };
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b[?1049h\x1b[?25l")?;
+        stdout.flush()?;
+        Ok(Terminal { original })
+    }
+
+    /// Replaces the contents of the screen with the given lines
+    pub fn draw(&mut self, screen: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        write!(stdout, "\x1b[H\x1b[2J")?;
+        // The terminal does not turn "\n" into a new line in raw mode
+        write!(stdout, "{}", screen.replace('\n', "\r\n"))?;
+        stdout.flush()
+    }
+
+    /// Waits up to `timeout` milliseconds for a key (None if none was pressed in that time)
+    pub fn readKey(&mut self, timeout: i32) -> io::Result<Option<Key>> {
+        let mut input = Self::nextByte;
+        match input(timeout)? {
+            Some(byte) => Ok(Some(decodeKey(byte, &mut input)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Waits up to `timeout` milliseconds for a byte of input (None if none came in that time)
+    fn nextByte(timeout: i32) -> io::Result<Option<u8>> {
+        if !Self::wait(timeout)? {
+            return Ok(None);
+        }
+        Self::readByte().map(Some)
+    }
+
+    /// Waits up to `timeout` milliseconds for input and tells if there is some
+    fn wait(timeout: i32) -> io::Result<bool> {
+        let mut descriptor = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        match unsafe { libc::poll(&mut descriptor, 1, timeout) } {
+            -1 => {
+                let error = io::Error::last_os_error();
+                // A resized terminal interrupts the wait
+                if error.kind() == io::ErrorKind::Interrupted {
+                    Ok(false)
+                } else {
+                    Err(error)
+                }
+            }
+            ready => Ok(ready > 0),
+        }
+    }
+
+    /// Reads one byte of input straight from the descriptor that `wait` polls, past any buffering
+    fn readByte() -> io::Result<u8> {
+        let mut byte = 0u8;
+        let read = unsafe { libc::read(libc::STDIN_FILENO, (&mut byte as *mut u8).cast(), 1) };
+        match read {
+            -1 => Err(io::Error::last_os_error()),
+            0 => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            _ => Ok(byte),
+        }
+    }
+}
+
+/// Turns the first byte of a key and what follows it in `input` into the key. `input` waits up to
+/// the given number of milliseconds for the next byte (None if none came in that time).
+fn decodeKey(first: u8, input: &mut impl FnMut(i32) -> io::Result<Option<u8>>) -> io::Result<Key> {
+    let key = match first {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x1b => escapeSequence(input)?,
+        byte @ 1..=26 => Key::Ctrl((b'a' + byte - 1) as char),
+        byte => Key::Char(byte as char),
+    };
+    Ok(key)
+}
+
+/// Reads the rest of an escape sequence from `input`: the arrow keys, or Escape on its own
+fn escapeSequence(input: &mut impl FnMut(i32) -> io::Result<Option<u8>>) -> io::Result<Key> {
+    if !matches!(input(ESCAPE_TIMEOUT)?, Some(b'[' | b'O')) {
+        return Ok(Key::Escape);
+    }
+    let key = match input(ESCAPE_TIMEOUT)? {
+        Some(b'A') => Key::Up,
+        Some(b'B') => Key::Down,
+        Some(b'C') => Key::Right,
+        Some(b'D') => Key::Left,
+        _ => Key::Escape,
+    };
+    Ok(key)
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "\x1b[?25h\x1b[?1049l");
+        let _ = stdout.flush();
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes the key that starts with the first of the given bytes
+    fn decode(bytes: &[u8]) -> Key {
+        let mut bytes = bytes.iter().copied();
+        let first = bytes.next().unwrap();
+        decodeKey(first, &mut |_| Ok(bytes.next())).unwrap()
+    }
+
+    #[test]
+    fn readsTheArrowKeys() {
+        assert_eq!(decode(b"\x1b[A"), Key::Up);
+        assert_eq!(decode(b"\x1b[B"), Key::Down);
+        assert_eq!(decode(b"\x1b[C"), Key::Right);
+        assert_eq!(decode(b"\x1b[D"), Key::Left);
+        assert_eq!(decode(b"\x1bOA"), Key::Up);
+    }
+
+    #[test]
+    fn readsEscapeOnItsOwn() {
+        assert_eq!(decode(b"\x1b"), Key::Escape);
+        assert_eq!(decode(b"\x1bx"), Key::Escape);
+        assert_eq!(decode(b"\x1b[Z"), Key::Escape);
+    }
+
+    #[test]
+    fn readsOtherKeys() {
+        assert_eq!(decode(b"\r"), Key::Enter);
+        assert_eq!(decode(b"\t"), Key::Tab);
+        assert_eq!(decode(&[3]), Key::Ctrl('c'));
+        assert_eq!(decode(b"h"), Key::Char('h'));
+    }
+}